@@ -0,0 +1,2 @@
+-- Readable text extracted from the linked page (or the HN post body for Ask/Show HN)
+ALTER TABLE articles ADD COLUMN content TEXT DEFAULT NULL;
@@ -0,0 +1,4 @@
+-- HN feed (top, new, best, ask, show, job) the story was first seen in
+ALTER TABLE articles ADD COLUMN feed TEXT NOT NULL DEFAULT 'top';
+
+CREATE INDEX IF NOT EXISTS idx_feed ON articles(feed);
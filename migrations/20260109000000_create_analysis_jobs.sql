@@ -0,0 +1,17 @@
+-- Analysis queue: one job per article, retried with backoff until done or dead-lettered
+CREATE TABLE IF NOT EXISTS analysis_jobs (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	article_id INTEGER NOT NULL UNIQUE REFERENCES articles(id) ON DELETE CASCADE,
+	status TEXT NOT NULL DEFAULT 'pending', -- pending, running, failed, done, dead
+	attempts INTEGER NOT NULL DEFAULT 0,
+	last_error TEXT,
+	next_run_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_due ON analysis_jobs(status, next_run_at);
+
+-- Existing articles keep their analysis state
+INSERT OR IGNORE INTO analysis_jobs (article_id, status)
+SELECT id, CASE WHEN ai_analysis_done = 1 THEN 'done' ELSE 'pending' END FROM articles;
@@ -0,0 +1,18 @@
+-- One row per fetch or analysis worker cycle
+CREATE TABLE IF NOT EXISTS aggregator_runs (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	kind TEXT NOT NULL, -- fetch, analysis
+	started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	finished_at TEXT,
+	duration_ms INTEGER,
+	stories_fetched INTEGER NOT NULL DEFAULT 0,
+	new_stories INTEGER NOT NULL DEFAULT 0,
+	updated_stories INTEGER NOT NULL DEFAULT 0,
+	analyzed INTEGER NOT NULL DEFAULT 0,
+	rejected INTEGER NOT NULL DEFAULT 0,
+	llm_errors INTEGER NOT NULL DEFAULT 0,
+	avg_llm_latency_ms INTEGER,
+	error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_aggregator_runs_started ON aggregator_runs(started_at DESC);
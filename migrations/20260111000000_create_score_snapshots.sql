@@ -0,0 +1,14 @@
+-- Score and comment count of an article at every fetch, to follow its trajectory
+CREATE TABLE IF NOT EXISTS score_snapshots (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	score INTEGER NOT NULL,
+	descendants INTEGER,
+	captured_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_score_snapshots_article ON score_snapshots(article_id, captured_at);
+
+-- Seed the history with the score we already know
+INSERT INTO score_snapshots (article_id, score, captured_at)
+SELECT id, score, fetched_at FROM articles;
@@ -0,0 +1,8 @@
+-- Full HN item metadata
+ALTER TABLE articles ADD COLUMN author TEXT;
+ALTER TABLE articles ADD COLUMN descendants INTEGER;
+ALTER TABLE articles ADD COLUMN item_type TEXT NOT NULL DEFAULT 'story';
+ALTER TABLE articles ADD COLUMN text TEXT;
+ALTER TABLE articles ADD COLUMN kids TEXT NOT NULL DEFAULT '[]'; -- JSON array of top-level comment ids
+ALTER TABLE articles ADD COLUMN dead BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE articles ADD COLUMN deleted BOOLEAN NOT NULL DEFAULT 0;
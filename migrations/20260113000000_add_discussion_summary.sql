@@ -0,0 +1,3 @@
+-- LLM digest of the HN comment thread, with the comment count it was made at
+ALTER TABLE articles ADD COLUMN discussion_summary TEXT;
+ALTER TABLE articles ADD COLUMN discussion_summary_comments INTEGER;
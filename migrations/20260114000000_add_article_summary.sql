@@ -0,0 +1,3 @@
+-- What the article is about, from the analyzer (driven by the page content when available)
+ALTER TABLE articles ADD COLUMN summary TEXT;
+ALTER TABLE articles ADD COLUMN key_points TEXT NOT NULL DEFAULT '[]'; -- JSON array of strings
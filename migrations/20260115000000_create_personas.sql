@@ -0,0 +1,58 @@
+-- Named interest profiles, synced from persona.txt ("default") and the personas/ directory at startup
+CREATE TABLE IF NOT EXISTS personas (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	name TEXT NOT NULL UNIQUE,
+	description TEXT NOT NULL,
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO personas (id, name, description) VALUES (1, 'default', '');
+
+-- Relevance of an article for one persona
+CREATE TABLE IF NOT EXISTS article_analyses (
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	persona_id INTEGER NOT NULL REFERENCES personas(id) ON DELETE CASCADE,
+	is_interesting BOOLEAN NOT NULL DEFAULT 0,
+	reason TEXT,
+	priority INTEGER,
+	category TEXT,
+	analyzed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (article_id, persona_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_article_analyses_persona ON article_analyses(persona_id, is_interesting, category);
+
+-- Existing analyses belong to the default persona
+INSERT INTO article_analyses (article_id, persona_id, is_interesting, reason, priority, category)
+SELECT id, 1, is_interesting, reason, priority, category FROM articles WHERE ai_analysis_done = 1;
+
+DROP INDEX IF EXISTS idx_is_interesting;
+DROP INDEX IF EXISTS idx_ai_analysis_done;
+ALTER TABLE articles DROP COLUMN ai_analysis_done;
+ALTER TABLE articles DROP COLUMN is_interesting;
+ALTER TABLE articles DROP COLUMN reason;
+ALTER TABLE articles DROP COLUMN priority;
+ALTER TABLE articles DROP COLUMN category;
+
+-- Analysis queue: one job per (article, persona)
+CREATE TABLE analysis_jobs_new (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	persona_id INTEGER NOT NULL REFERENCES personas(id) ON DELETE CASCADE,
+	status TEXT NOT NULL DEFAULT 'pending', -- pending, running, failed, done, dead
+	attempts INTEGER NOT NULL DEFAULT 0,
+	last_error TEXT,
+	next_run_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	UNIQUE(article_id, persona_id)
+);
+
+INSERT INTO analysis_jobs_new (id, article_id, persona_id, status, attempts, last_error, next_run_at, created_at, updated_at)
+SELECT id, article_id, 1, status, attempts, last_error, next_run_at, created_at, updated_at FROM analysis_jobs;
+
+DROP TABLE analysis_jobs;
+ALTER TABLE analysis_jobs_new RENAME TO analysis_jobs;
+
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_due ON analysis_jobs(status, next_run_at);
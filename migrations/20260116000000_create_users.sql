@@ -0,0 +1,19 @@
+-- Local accounts, passwords are argon2 PHC strings
+CREATE TABLE IF NOT EXISTS users (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+	password_hash TEXT NOT NULL,
+	persona_id INTEGER REFERENCES personas(id) ON DELETE SET NULL, -- preferred persona
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Login sessions, only the SHA-256 of the cookie token is stored
+CREATE TABLE IF NOT EXISTS sessions (
+	token_hash TEXT PRIMARY KEY,
+	user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user ON sessions(user_id);
+CREATE INDEX IF NOT EXISTS idx_sessions_expires ON sessions(expires_at);
@@ -0,0 +1,7 @@
+-- Articles a reader has opened or marked read; user_id 0 is the shared state of visitors who are not signed in
+CREATE TABLE IF NOT EXISTS article_reads (
+	user_id INTEGER NOT NULL,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	read_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (user_id, article_id)
+);
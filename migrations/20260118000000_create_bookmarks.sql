@@ -0,0 +1,13 @@
+-- Reading list, user_id 0 is the shared list of visitors who are not signed in.
+-- RESTRICT keeps any future article cleanup from dropping saved articles.
+CREATE TABLE IF NOT EXISTS bookmarks (
+	user_id INTEGER NOT NULL,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE RESTRICT,
+	note TEXT NOT NULL DEFAULT '',
+	tags TEXT NOT NULL DEFAULT '[]', -- JSON array of lowercase tags
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (user_id, article_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bookmarks_article ON bookmarks(article_id);
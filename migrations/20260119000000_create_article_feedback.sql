@@ -0,0 +1,12 @@
+-- Thumbs up (1) or down (-1) on an article's relevance for a persona, user_id 0 is visitors who are not signed in
+CREATE TABLE IF NOT EXISTS article_feedback (
+	user_id INTEGER NOT NULL,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	persona_id INTEGER NOT NULL REFERENCES personas(id) ON DELETE CASCADE,
+	vote INTEGER NOT NULL CHECK (vote IN (-1, 1)),
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (user_id, article_id, persona_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_article_feedback_persona ON article_feedback(persona_id, updated_at);
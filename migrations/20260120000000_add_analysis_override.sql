@@ -0,0 +1,2 @@
+-- Set when a human edited the relevance, priority or category; re-analysis then leaves the row alone
+ALTER TABLE article_analyses ADD COLUMN overridden BOOLEAN NOT NULL DEFAULT 0;
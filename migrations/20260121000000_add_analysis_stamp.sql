@@ -0,0 +1,2 @@
+-- Hash of what produced the analysis (persona, categories, model, prompt version); NULL for analyses made before stamping
+ALTER TABLE article_analyses ADD COLUMN analysis_stamp TEXT;
@@ -0,0 +1,3 @@
+-- Unsuccessful discussion summaries (no comments, empty answer, analyzer error) back off instead of being retried every cycle
+ALTER TABLE articles ADD COLUMN discussion_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE articles ADD COLUMN discussion_retry_at TEXT;
@@ -0,0 +1,2 @@
+-- Personas removed from the config are deactivated rather than deleted, so their analyses and votes survive a temporary removal
+ALTER TABLE personas ADD COLUMN active BOOLEAN NOT NULL DEFAULT 1;
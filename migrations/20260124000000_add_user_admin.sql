@@ -0,0 +1,4 @@
+-- Admins may trigger fetches and re-analysis; the first account of an instance is one
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE users SET is_admin = 1 WHERE id = (SELECT MIN(id) FROM users);
@@ -0,0 +1,17 @@
+-- Visitors who are not signed in share user_id 0; give them a placeholder account nobody can sign in to
+-- so per-user tables can reference users and go away with the account
+INSERT OR IGNORE INTO users (id, username, password_hash) VALUES (0, '(anonymous)', '!');
+
+CREATE TABLE article_reads_new (
+	user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+	read_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (user_id, article_id)
+);
+
+-- Marks left behind by accounts deleted before the constraint existed are dropped
+INSERT INTO article_reads_new (user_id, article_id, read_at)
+SELECT user_id, article_id, read_at FROM article_reads WHERE user_id IN (SELECT id FROM users);
+
+DROP TABLE article_reads;
+ALTER TABLE article_reads_new RENAME TO article_reads;
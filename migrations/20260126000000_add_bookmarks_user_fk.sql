@@ -0,0 +1,20 @@
+-- Bookmarks go away with their account (user_id 0 is the placeholder for visitors who are not signed in).
+-- Query contract: article cleanup must skip saved articles, e.g. `AND id NOT IN (SELECT article_id FROM bookmarks)`,
+-- as prune_score_snapshots does; RESTRICT makes a cleanup that forgets fail instead of emptying reading lists.
+CREATE TABLE bookmarks_new (
+	user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE RESTRICT,
+	note TEXT NOT NULL DEFAULT '',
+	tags TEXT NOT NULL DEFAULT '[]', -- JSON array of lowercase tags
+	created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	PRIMARY KEY (user_id, article_id)
+);
+
+INSERT INTO bookmarks_new (user_id, article_id, note, tags, created_at, updated_at)
+SELECT user_id, article_id, note, tags, created_at, updated_at FROM bookmarks WHERE user_id IN (SELECT id FROM users);
+
+DROP TABLE bookmarks;
+ALTER TABLE bookmarks_new RENAME TO bookmarks;
+
+CREATE INDEX IF NOT EXISTS idx_bookmarks_article ON bookmarks(article_id);
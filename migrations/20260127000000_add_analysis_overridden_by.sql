@@ -0,0 +1,2 @@
+-- Who hand-edited the analysis (0 for visitors who are not signed in), cleared with the override
+ALTER TABLE article_analyses ADD COLUMN overridden_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
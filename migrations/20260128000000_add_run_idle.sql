@@ -0,0 +1,2 @@
+-- Analysis cycles that found nothing to do; kept in the history, hidden from it by default
+ALTER TABLE aggregator_runs ADD COLUMN idle BOOLEAN NOT NULL DEFAULT 0;
@@ -5,9 +5,17 @@ use leptos_router::{
 	StaticSegment,
 };
 
-use crate::components::{article_list::ArticleList, sort_controls::SortControls, theme_toggle::ThemeToggle};
-use crate::db::models::{SortDirection, SortField};
-use crate::server_fns::articles::{get_interesting_articles};
+use crate::components::{
+	analyzer_status::AnalyzerStatus, article_list::ArticleList, cycle_status::CycleStatus, persona_switcher::PersonaSwitcher, run_history::RunHistory, saved_list::SavedList, sort_controls::SortControls,
+	theme_toggle::ThemeToggle, unread_badge::UnreadBadge, user_menu::UserMenu,
+};
+use crate::db::models::{HnFeed, SortDirection, SortField};
+use crate::server_fns::{
+	articles::{get_interesting_articles, get_rejected_articles, get_unread_count, MarkAllRead, MarkArticlesRead, OverrideAnalysis, ResetAnalysis},
+	auth::{get_current_user, Login, Register},
+	bookmarks::get_bookmarks,
+	runs::get_recent_runs,
+};
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
 	view! {
@@ -44,6 +52,15 @@ pub fn App() -> impl IntoView {
 					<Route path=StaticSegment("") view=move || view! {
 						<HomePage dark_mode=dark_mode.into() set_dark_mode=set_dark_mode />
 					}/>
+					<Route path=StaticSegment("runs") view=move || view! {
+						<RunsPage dark_mode=dark_mode.into() set_dark_mode=set_dark_mode />
+					}/>
+					<Route path=StaticSegment("saved") view=move || view! {
+						<SavedPage dark_mode=dark_mode.into() set_dark_mode=set_dark_mode />
+					}/>
+					<Route path=StaticSegment("login") view=move || view! {
+						<LoginPage dark_mode=dark_mode.into() set_dark_mode=set_dark_mode />
+					}/>
 				</Routes>
 			</main>
 		</Router>
@@ -52,26 +69,78 @@ pub fn App() -> impl IntoView {
 
 #[component]
 fn HomePage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl IntoView {
+	let current_user = Resource::new(|| (), |_| get_current_user());
+	// Empty until the user picks one, the server then falls back to their saved persona
+	let (selected_persona, set_selected_persona) = signal(String::new());
 	let (sort_field, set_sort_field) = signal(SortField::Date);
 	let (sort_direction, set_sort_direction) = signal(SortDirection::Descending);
 	let (selected_category, set_selected_category) = signal(String::new());
+	let (selected_feed, set_selected_feed) = signal(None::<HnFeed>);
 
-	let articles = Resource::new(move || (sort_field.get(), sort_direction.get(), selected_category.get()), |(field, direction, cat)| get_interesting_articles(field, direction, cat));
+	let (hide_read, set_hide_read) = signal(false);
+	let (show_rejected, set_show_rejected) = signal(false);
+
+	let mark_read = ServerAction::<MarkArticlesRead>::new();
+	let mark_all_read = ServerAction::<MarkAllRead>::new();
+	let override_analysis = ServerAction::<OverrideAnalysis>::new();
+	let reset_analysis = ServerAction::<ResetAnalysis>::new();
+	let edits = move || (mark_all_read.version().get(), override_analysis.version().get(), reset_analysis.version().get());
+	let can_edit = Signal::derive(move || current_user.get().and_then(Result::ok).flatten().is_some_and(|user| user.is_admin));
+
+	// Reloaded after "mark all read" and analysis edits, single clicks only update their card and the badge
+	let articles = Resource::new(
+		move || (selected_persona.get(), show_rejected.get(), hide_read.get(), sort_field.get(), sort_direction.get(), selected_category.get(), selected_feed.get(), edits()),
+		|(persona, rejected, hide_read, field, direction, cat, feed, _)| async move {
+			if rejected {
+				get_rejected_articles(persona, hide_read, field, direction, cat, feed).await
+			} else {
+				get_interesting_articles(persona, hide_read, field, direction, cat, feed).await
+			}
+		},
+	);
+	// Counts what the current filters list, like "mark all read" marks
+	let unread = Resource::new(
+		move || (selected_persona.get(), show_rejected.get(), selected_category.get(), selected_feed.get(), mark_read.version().get(), mark_all_read.version().get()),
+		|(persona, rejected, cat, feed, _, _)| get_unread_count(persona, rejected, cat, feed),
+	);
 
 	view! {
 		<div class="container">
 			<header class="header">
 				<h1>"Smart HN Aggregator"</h1>
-				<ThemeToggle dark_mode=dark_mode set_dark_mode=set_dark_mode />
+				<div class="header-actions">
+					<UnreadBadge
+						persona=selected_persona.into()
+						show_rejected=show_rejected.into()
+						selected_category=selected_category.into()
+						selected_feed=selected_feed.into()
+						unread=unread
+						mark_all_read=mark_all_read
+					/>
+					<PersonaSwitcher selected_persona=selected_persona.into() set_selected_persona=set_selected_persona current_user=current_user />
+					<CycleStatus current_user=current_user />
+					<AnalyzerStatus />
+					<a class="nav-link" href="/saved">"Saved"</a>
+					<a class="nav-link" href="/runs">"Run history"</a>
+					<UserMenu current_user=current_user />
+					<ThemeToggle dark_mode=dark_mode set_dark_mode=set_dark_mode />
+				</div>
 			</header>
 
 			<SortControls
+				persona=selected_persona.into()
 				sort_field=sort_field.into()
 				set_sort_field=set_sort_field
 				sort_direction=sort_direction.into()
 				set_sort_direction=set_sort_direction
 				selected_category=selected_category.into()
 				set_selected_category=set_selected_category
+				selected_feed=selected_feed.into()
+				set_selected_feed=set_selected_feed
+				hide_read=hide_read.into()
+				set_hide_read=set_hide_read
+				show_rejected=show_rejected.into()
+				set_show_rejected=set_show_rejected
 			/>
 
 			<Suspense fallback=|| view! { <div class="loading">"Loading articles…"</div> }>
@@ -79,7 +148,15 @@ fn HomePage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl I
 					articles.get().map(|result| {
 						match result {
 							Ok(articles) => view! {
-								<ArticleList articles=articles />
+								<ArticleList
+									articles=articles
+									rejected=show_rejected.get_untracked()
+									persona=selected_persona.into()
+									mark_read=mark_read
+									override_analysis=override_analysis
+									reset_analysis=reset_analysis
+									can_edit=can_edit
+								/>
 							}.into_any(),
 							Err(e) => view! {
 								<div class="error">
@@ -93,3 +170,134 @@ fn HomePage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl I
 		</div>
 	}
 }
+
+#[component]
+fn RunsPage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl IntoView {
+	let (show_idle, set_show_idle) = signal(false);
+	let runs = Resource::new(move || show_idle.get(), |include_idle| get_recent_runs(100, include_idle));
+
+	view! {
+		<div class="container wide">
+			<header class="header">
+				<h1>"Aggregator runs"</h1>
+				<div class="header-actions">
+					<label class="toggle" title="Analysis cycles that found nothing to do">
+						<input type="checkbox" prop:checked=move || show_idle.get() on:change=move |ev| set_show_idle.set(event_target_checked(&ev)) />
+						"Show idle cycles"
+					</label>
+					<a class="nav-link" href="/">"← Articles"</a>
+					<ThemeToggle dark_mode=dark_mode set_dark_mode=set_dark_mode />
+				</div>
+			</header>
+
+			<Suspense fallback=|| view! { <div class="loading">"Loading runs…"</div> }>
+				{move || {
+					runs.get().map(|result| {
+						match result {
+							Ok(runs) => view! {
+								<RunHistory runs=runs />
+							}.into_any(),
+							Err(e) => view! {
+								<div class="error">
+									<p>"Error loading runs: " {e.to_string()}</p>
+								</div>
+							}.into_any(),
+						}
+					})
+				}}
+			</Suspense>
+		</div>
+	}
+}
+
+#[component]
+fn SavedPage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl IntoView {
+	// The reader's saved persona decides which analysis is shown
+	let mark_read = ServerAction::<MarkArticlesRead>::new();
+	let override_analysis = ServerAction::<OverrideAnalysis>::new();
+	let reset_analysis = ServerAction::<ResetAnalysis>::new();
+	let bookmarks = Resource::new(move || (override_analysis.version().get(), reset_analysis.version().get()), |_| get_bookmarks(String::new()));
+	let current_user = Resource::new(|| (), |_| get_current_user());
+	let can_edit = Signal::derive(move || current_user.get().and_then(Result::ok).flatten().is_some_and(|user| user.is_admin));
+
+	view! {
+		<div class="container">
+			<header class="header">
+				<h1>"Reading list"</h1>
+				<div class="header-actions">
+					<a class="nav-link" href="/">"← Articles"</a>
+					<UserMenu current_user=current_user />
+					<ThemeToggle dark_mode=dark_mode set_dark_mode=set_dark_mode />
+				</div>
+			</header>
+
+			<Suspense fallback=|| view! { <div class="loading">"Loading saved articles…"</div> }>
+				{move || {
+					bookmarks.get().map(|result| {
+						match result {
+							Ok(bookmarks) => view! {
+								<SavedList bookmarks=bookmarks mark_read=mark_read override_analysis=override_analysis reset_analysis=reset_analysis can_edit=can_edit />
+							}.into_any(),
+							Err(e) => view! {
+								<div class="error">
+									<p>"Error loading saved articles: " {e.to_string()}</p>
+								</div>
+							}.into_any(),
+						}
+					})
+				}}
+			</Suspense>
+		</div>
+	}
+}
+
+#[component]
+fn LoginPage(dark_mode: Signal<bool>, set_dark_mode: WriteSignal<bool>) -> impl IntoView {
+	let login = ServerAction::<Login>::new();
+	let register = ServerAction::<Register>::new();
+
+	let error_message = |result: MappedSignal<Option<Result<(), ServerFnError>>>| {
+		move || match result.get() {
+			Some(Err(e)) => Some(view! { <p class="form-error">{server_error_message(e)}</p> }),
+			_ => None,
+		}
+	};
+
+	view! {
+		<div class="container">
+			<header class="header">
+				<h1>"Sign in"</h1>
+				<div class="header-actions">
+					<a class="nav-link" href="/">"← Articles"</a>
+					<ThemeToggle dark_mode=dark_mode set_dark_mode=set_dark_mode />
+				</div>
+			</header>
+
+			<div class="auth-forms">
+				<ActionForm action=login attr:class="auth-form">
+					<h2>"Log in"</h2>
+					<input type="text" name="username" placeholder="Username" autocomplete="username" required/>
+					<input type="password" name="password" placeholder="Password" autocomplete="current-password" required/>
+					<button type="submit" disabled=move || login.pending().get()>"Log in"</button>
+					{error_message(login.value())}
+				</ActionForm>
+
+				<ActionForm action=register attr:class="auth-form">
+					<h2>"Create an account"</h2>
+					<input type="text" name="username" placeholder="Username" autocomplete="username" minlength="3" maxlength="32" required/>
+					<input type="password" name="password" placeholder="Password (8+ characters)" autocomplete="new-password" minlength="8" required/>
+					<button type="submit" disabled=move || register.pending().get()>"Register"</button>
+					{error_message(register.value())}
+				</ActionForm>
+			</div>
+		</div>
+	}
+}
+
+// Strip the "error running server function" prefix from user-facing messages
+fn server_error_message(error: ServerFnError) -> String {
+	match error {
+		ServerFnError::ServerError(message) => message,
+		other => other.to_string(),
+	}
+}
@@ -0,0 +1,231 @@
+#[cfg(feature = "ssr")]
+use std::{
+	collections::HashMap,
+	net::IpAddr,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use argon2::{
+	password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+	Argon2,
+};
+
+#[cfg(feature = "ssr")]
+use axum::{
+	extract::{Request, State},
+	http::{header, request::Parts, HeaderMap, StatusCode},
+	middleware::Next,
+	response::{IntoResponse, Redirect, Response},
+};
+
+#[cfg(feature = "ssr")]
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "ssr")]
+use crate::{
+	db::{models::CurrentUser, repository},
+	state::AppState,
+};
+
+#[cfg(feature = "ssr")]
+pub const SESSION_COOKIE: &str = "hn_session";
+
+// Owner of per-user state (read marks…) created by visitors who are not signed in
+#[cfg(feature = "ssr")]
+pub const ANONYMOUS_USER_ID: i64 = 0;
+
+// Reachable without a session even when AUTH_REQUIRED is set
+#[cfg(feature = "ssr")]
+const PUBLIC_PATHS: &[&str] = &["/login", "/api/login", "/api/register", "/api/current_user", "/favicon.ico", "/healthz", "/readyz"];
+
+// Guarded by METRICS_TOKEN instead of a session when one is configured
+#[cfg(feature = "ssr")]
+const METRICS_PATH: &str = "/metrics";
+
+// Checked instead when the username doesn't exist, so unknown users take as long to reject as wrong passwords
+#[cfg(feature = "ssr")]
+pub const DUMMY_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$aG4tc21hcnQtZHVtbXktc2FsdA$LwjsqvixT1czhLRK+n0fsqrVmdIY8mCR1Hz/0wNiBKE";
+
+// Hashing time grows with the input, longer passwords are refused before it
+#[cfg(feature = "ssr")]
+pub const MAX_PASSWORD_CHARS: usize = 1024;
+
+// Failed logins allowed per username and per client address within LOGIN_THROTTLE_WINDOW
+#[cfg(feature = "ssr")]
+const MAX_FAILURES_PER_USERNAME: u32 = 5;
+
+// Higher than per username, a reverse proxy puts every visitor behind one address
+#[cfg(feature = "ssr")]
+const MAX_FAILURES_PER_ADDRESS: u32 = 50;
+
+#[cfg(feature = "ssr")]
+const LOGIN_THROTTLE_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+// Argon2 is deliberately slow, keep it off the async workers
+#[cfg(feature = "ssr")]
+pub async fn hash_password(password: String) -> Result<String> {
+	tokio::task::spawn_blocking(move || {
+		let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>()).map_err(|e| anyhow::anyhow!("Failed to encode salt: {}", e))?;
+		let hash = Argon2::default().hash_password(password.as_bytes(), &salt).map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
+		Ok(hash.to_string())
+	})
+	.await?
+}
+
+#[cfg(feature = "ssr")]
+pub async fn verify_password(password: String, password_hash: String) -> bool {
+	tokio::task::spawn_blocking(move || PasswordHash::new(&password_hash).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())).await.unwrap_or(false)
+}
+
+// Counts failed logins per username and per client address, in memory, over a fixed window
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+pub struct LoginThrottle {
+	failures: Mutex<HashMap<String, (u32, Instant)>>,
+}
+
+#[cfg(feature = "ssr")]
+impl LoginThrottle {
+	pub fn allow(&self, username: &str, address: Option<IpAddr>) -> bool {
+		let failures = self.failures.lock().unwrap();
+		let exhausted = |key: String, limit: u32| failures.get(&key).is_some_and(|(count, since)| since.elapsed() < LOGIN_THROTTLE_WINDOW && *count >= limit);
+		!(exhausted(username_key(username), MAX_FAILURES_PER_USERNAME) || address.is_some_and(|address| exhausted(address_key(address), MAX_FAILURES_PER_ADDRESS)))
+	}
+
+	pub fn record_failure(&self, username: &str, address: Option<IpAddr>) {
+		let mut failures = self.failures.lock().unwrap();
+		// Expired windows are dropped here, so the map only holds recent offenders
+		failures.retain(|_, (_, since)| since.elapsed() < LOGIN_THROTTLE_WINDOW);
+		for key in [Some(username_key(username)), address.map(address_key)].into_iter().flatten() {
+			failures.entry(key).or_insert((0, Instant::now())).0 += 1;
+		}
+	}
+
+	// Only the username is cleared, one good password shouldn't unlock guessing from the same address
+	pub fn record_success(&self, username: &str) {
+		self.failures.lock().unwrap().remove(&username_key(username));
+	}
+}
+
+#[cfg(feature = "ssr")]
+fn username_key(username: &str) -> String {
+	format!("user:{}", username.to_lowercase())
+}
+
+#[cfg(feature = "ssr")]
+fn address_key(address: IpAddr) -> String {
+	format!("addr:{}", address)
+}
+
+// Random cookie value; only its digest is stored, so a leaked database can't be replayed
+#[cfg(feature = "ssr")]
+pub fn new_session_token() -> String {
+	rand::random::<[u8; 32]>().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(feature = "ssr")]
+pub fn token_digest(token: &str) -> String {
+	Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(feature = "ssr")]
+pub fn session_cookie(token: &str, max_age_secs: u64, secure: bool) -> String {
+	format!("{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}", SESSION_COOKIE, token, max_age_secs, if secure { "; Secure" } else { "" })
+}
+
+#[cfg(feature = "ssr")]
+pub fn session_token(headers: &HeaderMap) -> Option<String> {
+	headers
+		.get_all(header::COOKIE)
+		.iter()
+		.filter_map(|value| value.to_str().ok())
+		.flat_map(|value| value.split(';'))
+		.filter_map(|pair| pair.trim().split_once('='))
+		.find(|(name, _)| *name == SESSION_COOKIE)
+		.map(|(_, value)| value.to_string())
+}
+
+// Resolves the session cookie into a CurrentUser request extension, and guards the app when AUTH_REQUIRED is set
+#[cfg(feature = "ssr")]
+pub async fn session_middleware(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+	let user = match session_token(request.headers()) {
+		Some(token) => repository::get_session_user(&state.db_pool, &token_digest(&token)).await.inspect_err(|e| tracing::warn!("Failed to load session: {}", e)).ok().flatten(),
+		None => None,
+	};
+
+	match user {
+		Some(user) => {
+			request.extensions_mut().insert(user);
+		}
+		None if state.config.auth_required && !is_public(request.uri().path()) && !(request.uri().path() == METRICS_PATH && state.config.metrics_token.is_some()) => {
+			return if request.uri().path().starts_with("/api/") { StatusCode::UNAUTHORIZED.into_response() } else { Redirect::to("/login").into_response() };
+		}
+		None => {}
+	}
+
+	next.run(request).await
+}
+
+#[cfg(feature = "ssr")]
+fn is_public(path: &str) -> bool {
+	PUBLIC_PATHS.contains(&path) || path.starts_with("/pkg/")
+}
+
+// The signed-in user of the current request, for server functions
+#[cfg(feature = "ssr")]
+pub fn current_user() -> Option<CurrentUser> {
+	leptos::prelude::use_context::<Parts>().and_then(|parts| parts.extensions.get::<CurrentUser>().cloned())
+}
+
+// For operational endpoints (refresh, re-analysis)
+#[cfg(feature = "ssr")]
+pub fn is_admin() -> bool {
+	current_user().is_some_and(|user| user.is_admin)
+}
+
+// Id per-user state is stored under, shared by every visitor who is not signed in
+#[cfg(feature = "ssr")]
+pub fn current_user_id() -> i64 {
+	current_user().map(|user| user.id).unwrap_or(ANONYMOUS_USER_ID)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn throttle_blocks_a_username_after_repeated_failures() {
+		let throttle = LoginThrottle::default();
+		for _ in 0..MAX_FAILURES_PER_USERNAME {
+			assert!(throttle.allow("Alice", None));
+			throttle.record_failure("Alice", None);
+		}
+		assert!(!throttle.allow("alice", None));
+		assert!(throttle.allow("bob", None));
+
+		throttle.record_success("alice");
+		assert!(throttle.allow("alice", None));
+	}
+
+	#[test]
+	fn throttle_blocks_an_address_across_usernames() {
+		let throttle = LoginThrottle::default();
+		let address: IpAddr = "203.0.113.7".parse().unwrap();
+		for i in 0..MAX_FAILURES_PER_ADDRESS {
+			throttle.record_failure(&format!("user{}", i), Some(address));
+		}
+		assert!(!throttle.allow("someone", Some(address)));
+		assert!(throttle.allow("someone", Some("203.0.113.8".parse().unwrap())));
+	}
+
+	#[tokio::test]
+	async fn dummy_hash_is_a_valid_argon2_hash() {
+		assert!(PasswordHash::new(DUMMY_PASSWORD_HASH).is_ok());
+		assert!(!verify_password("password".to_string(), DUMMY_PASSWORD_HASH.to_string()).await);
+	}
+}
@@ -0,0 +1,72 @@
+use crate::server_fns::articles::{get_categories, OverrideAnalysis, ResetAnalysis};
+use leptos::prelude::*;
+
+// Inline form overriding the analyzer's verdict for the current persona
+#[component]
+pub fn AnalysisEditor(
+	article_id: i64,
+	persona: Signal<String>,
+	is_interesting: bool,
+	priority: Option<i64>,
+	category: Option<String>,
+	overridden: bool,
+	override_analysis: ServerAction<OverrideAnalysis>,
+	reset_analysis: ServerAction<ResetAnalysis>,
+	set_editing: WriteSignal<bool>,
+) -> impl IntoView {
+	let categories = Resource::new(|| (), |_| get_categories());
+
+	let (relevant, set_relevant) = signal(is_interesting);
+	let (new_priority, set_new_priority) = signal(priority.unwrap_or(3));
+	let (new_category, set_new_category) = signal(category.unwrap_or_else(|| "Other".to_string()));
+
+	let save = move |_| {
+		override_analysis.dispatch(OverrideAnalysis {
+			article_id,
+			persona: persona.get_untracked(),
+			is_interesting: relevant.get_untracked(),
+			priority: new_priority.get_untracked(),
+			category: new_category.get_untracked(),
+		});
+		set_editing.set(false);
+	};
+	let reset = move |_| {
+		reset_analysis.dispatch(ResetAnalysis { article_id, persona: persona.get_untracked() });
+		set_editing.set(false);
+	};
+
+	view! {
+		<div class="analysis-editor">
+			<select aria-label="Relevance" on:change=move |ev| set_relevant.set(event_target_value(&ev) == "yes")>
+				<option value="yes" selected=move || relevant.get()>"Relevant"</option>
+				<option value="no" selected=move || !relevant.get()>"Not relevant"</option>
+			</select>
+			<select aria-label="Priority" on:change=move |ev| set_new_priority.set(event_target_value(&ev).parse().unwrap_or(3))>
+				{(1..=5)
+					.map(|p| view! { <option value=p selected=move || new_priority.get() == p>{format!("Priority {}", p)}</option> })
+					.collect_view()}
+			</select>
+			<select aria-label="Category" on:change=move |ev| set_new_category.set(event_target_value(&ev))>
+				<Suspense fallback=|| view! { <option>"Loading…"</option> }>
+					{move || Suspend::new(async move {
+						categories
+							.await
+							.unwrap_or_default()
+							.into_iter()
+							.map(|cat| {
+								let is_selected = {
+									let cat = cat.clone();
+									move || new_category.with(|selected| selected.eq_ignore_ascii_case(&cat))
+								};
+								view! { <option value=cat.clone() selected=is_selected>{cat.clone()}</option> }
+							})
+							.collect_view()
+					})}
+				</Suspense>
+			</select>
+			<button class="save" on:click=save>"Save"</button>
+			{overridden.then(|| view! { <button on:click=reset title="Drop the override and analyze the article again">"Reset to AI"</button> })}
+			<button on:click=move |_| set_editing.set(false)>"Cancel"</button>
+		</div>
+	}
+}
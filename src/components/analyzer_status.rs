@@ -0,0 +1,29 @@
+use crate::db::models::BreakerState;
+use crate::server_fns::status::get_analyzer_status;
+use leptos::prelude::*;
+
+#[component]
+pub fn AnalyzerStatus() -> impl IntoView {
+	let status = Resource::new(|| (), |_| get_analyzer_status());
+
+	view! {
+		<Suspense fallback=|| ()>
+			{move || Suspend::new(async move {
+				match status.await {
+					Ok(s) if s.state == BreakerState::Open => {
+						let minutes = s.retry_in_secs.unwrap_or(0).div_ceil(60);
+						view! {
+							<span class="analyzer-status paused" title=format!("{} consecutive failures", s.consecutive_failures)>
+								{format!("Analyzer paused, retrying in {} min", minutes)}
+							</span>
+						}.into_any()
+					}
+					Ok(s) if s.state == BreakerState::HalfOpen => view! {
+						<span class="analyzer-status probing">"Analyzer recovering…"</span>
+					}.into_any(),
+					_ => ().into_any(),
+				}
+			})}
+		</Suspense>
+	}
+}
@@ -1,26 +1,121 @@
+use crate::components::{analysis_editor::AnalysisEditor, sparkline::Sparkline};
 use crate::db::models::Article;
+use crate::server_fns::articles::{MarkArticlesRead, OverrideAnalysis, ResetAnalysis, SetFeedback};
+use crate::server_fns::bookmarks::{AddBookmark, RemoveBookmark};
 use leptos::prelude::*;
 
 #[component]
-pub fn ArticleCard(article: Article) -> impl IntoView {
+pub fn ArticleCard(
+	article: Article,
+	persona: Signal<String>,
+	mark_read: ServerAction<MarkArticlesRead>,
+	override_analysis: ServerAction<OverrideAnalysis>,
+	reset_analysis: ServerAction<ResetAnalysis>,
+	// Analyses are shared, only admins may edit them
+	can_edit: Signal<bool>,
+) -> impl IntoView {
+	// Ask HN and other text posts have no URL, their title leads to the discussion instead
+	let discussion_url = article.discussion_url();
+	let link = article.url.clone().unwrap_or_else(|| discussion_url.clone());
+	let comments = match article.descendants.unwrap_or(0) {
+		1 => "1 comment".to_string(),
+		n => format!("{} comments", n),
+	};
+
+	// Flipped locally on click, the list isn't reloaded for every article opened
+	let is_read = RwSignal::new(article.is_read);
+	let article_id = article.id;
+	let on_open = move |_| {
+		if !is_read.get_untracked() {
+			is_read.set(true);
+			mark_read.dispatch(MarkArticlesRead { article_ids: vec![article_id] });
+		}
+	};
+
+	let is_bookmarked = RwSignal::new(article.is_bookmarked);
+	let add_bookmark = ServerAction::<AddBookmark>::new();
+	let remove_bookmark = ServerAction::<RemoveBookmark>::new();
+	let toggle_bookmark = move |_| {
+		if is_bookmarked.get_untracked() {
+			remove_bookmark.dispatch(RemoveBookmark { article_id });
+		} else {
+			add_bookmark.dispatch(AddBookmark { article_id, note: String::new(), tags: String::new() });
+		}
+		is_bookmarked.update(|saved| *saved = !*saved);
+	};
+
+	// Clicking the active thumb again clears the vote
+	let feedback = RwSignal::new(article.feedback);
+	let set_feedback = ServerAction::<SetFeedback>::new();
+	let vote = move |value: i64| {
+		let value = if feedback.get_untracked() == value { 0 } else { value };
+		set_feedback.dispatch(SetFeedback { article_id, persona: persona.get_untracked(), vote: value });
+		feedback.set(value);
+	};
+
+	let (editing, set_editing) = signal(false);
+	let rejected = article.ai_analysis_done && !article.is_interesting;
+	let (is_interesting, priority, category, overridden) = (article.is_interesting, article.priority, article.category.clone(), article.overridden);
+
 	view! {
-		<div class="article-card">
+		<div class="article-card" class:read=move || is_read.get() class:rejected=rejected>
 			<h3>
-				<a href={article.url.clone().unwrap_or_default()} target="_blank" rel="noopener noreferrer">
+				<a href={link} target="_blank" rel="noopener noreferrer" on:click=on_open on:auxclick=on_open>
 					{article.title.clone()}
 				</a>
 			</h3>
 			<div class="meta">
 				<span class="score">"⬆ " {article.score} " points"</span>
+				<span class="velocity" title="Points per hour since posting">{format!("{:.1} pts/h", article.velocity)}</span>
+				<Sparkline values=article.score_history />
+				{article.author.clone().map(|author| view! { <span class="author">"by " {author}</span> })}
+				<a class="comments" href={discussion_url} target="_blank" rel="noopener noreferrer">"💬 " {comments}</a>
 				{article.priority.map(|p| view! {
 					<span class="priority">"Priority: " {p} "/5"</span>
 				})}
 				<span>{article.category}</span>
+				{rejected.then(|| view! { <span class="rejected-badge" title="Filtered out by the analyzer, see the reason below">"✗ Rejected"</span> })}
+				{article.overridden.then(|| view! { <span class="overridden" title="Set by hand, re-analysis keeps it">"✎ edited"</span> })}
+				<span class="feedback" title="Tell the analyzer whether this should have been picked">
+					<button class:active=move || feedback.get() == 1 on:click=move |_| vote(1) aria-label="Relevant">"👍"</button>
+					<button class:active=move || feedback.get() == -1 on:click=move |_| vote(-1) aria-label="Not relevant">"👎"</button>
+				</span>
+				<Show when=move || can_edit.get()>
+					<button class="edit-toggle" on:click=move |_| set_editing.update(|e| *e = !*e) title="Override relevance, priority or category">"✎ Edit"</button>
+				</Show>
+				<button class="bookmark-toggle" class:saved=move || is_bookmarked.get() on:click=toggle_bookmark title="Save to your reading list">
+					{move || if is_bookmarked.get() { "★ Saved" } else { "☆ Save" }}
+				</button>
 			</div>
+			<Show when=move || editing.get() && can_edit.get()>
+				<AnalysisEditor
+					article_id=article_id
+					persona=persona
+					is_interesting=is_interesting
+					priority=priority
+					category=category.clone()
+					overridden=overridden
+					override_analysis=override_analysis
+					reset_analysis=reset_analysis
+					set_editing=set_editing
+				/>
+			</Show>
+			{article.summary.clone().map(|summary| view! { <p class="summary">{summary}</p> })}
+			{(!article.key_points.is_empty()).then(|| view! {
+				<ul class="key-points">
+					{article.key_points.clone().into_iter().map(|point| view! { <li>{point}</li> }).collect_view()}
+				</ul>
+			})}
 			{article.reason.as_ref().map(|reason| {
 				let r = reason.clone();
 				view! { <p class="reason">{r}</p> }
 			})}
+			{article.discussion_summary.clone().map(|summary| view! {
+				<details class="discussion">
+					<summary>"Discussion summary"</summary>
+					<p>{summary}</p>
+				</details>
+			})}
 		</div>
 	}
 }
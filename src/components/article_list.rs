@@ -1,13 +1,24 @@
 use crate::components::article_card::ArticleCard;
 use crate::db::models::Article;
+use crate::server_fns::articles::{MarkArticlesRead, OverrideAnalysis, ResetAnalysis};
 use leptos::prelude::*;
 
 #[component]
-pub fn ArticleList(articles: Vec<Article>) -> impl IntoView {
+pub fn ArticleList(
+	articles: Vec<Article>,
+	// Listing what the analyzer filtered out
+	rejected: bool,
+	persona: Signal<String>,
+	mark_read: ServerAction<MarkArticlesRead>,
+	override_analysis: ServerAction<OverrideAnalysis>,
+	reset_analysis: ServerAction<ResetAnalysis>,
+	can_edit: Signal<bool>,
+) -> impl IntoView {
 	if articles.is_empty() {
+		let message = if rejected { "No rejected articles match these filters." } else { "No interesting articles yet. The background worker is fetching and analyzing articles..." };
 		view! {
 			<div class="empty-state">
-				<p>"No interesting articles yet. The background worker is fetching and analyzing articles..."</p>
+				<p>{message}</p>
 			</div>
 		}
 		.into_any()
@@ -15,7 +26,7 @@ pub fn ArticleList(articles: Vec<Article>) -> impl IntoView {
 		view! {
 			<div class="article-list">
 				{articles.into_iter()
-					.map(|article| view! { <ArticleCard article=article /> })
+					.map(|article| view! { <ArticleCard article=article persona=persona mark_read=mark_read override_analysis=override_analysis reset_analysis=reset_analysis can_edit=can_edit /> })
 					.collect_view()}
 			</div>
 		}
@@ -0,0 +1,71 @@
+use crate::db::models::{CurrentUser, WorkerRunStatus};
+use crate::server_fns::status::{get_aggregator_status, TriggerRefresh};
+use leptos::prelude::*;
+
+#[component]
+pub fn CycleStatus(current_user: Resource<Result<Option<CurrentUser>, ServerFnError>>) -> impl IntoView {
+	let refresh = ServerAction::<TriggerRefresh>::new();
+	let status = Resource::new(move || refresh.version().get(), |_| get_aggregator_status());
+
+	view! {
+		<div class="cycle-status">
+			<Suspense fallback=|| ()>
+				{move || Suspend::new(async move {
+					match status.await {
+						Ok(s) => view! {
+							<span class="cycle-summary" title=run_details("Fetch", &s.fetch) + "\n" + &run_details("Analysis", &s.analysis)>
+								{summary(&s.fetch, &s.analysis)}
+							</span>
+						}.into_any(),
+						Err(_) => ().into_any(),
+					}
+				})}
+			</Suspense>
+			// Only admins may trigger a fetch
+			<Suspense fallback=|| ()>
+				{move || Suspend::new(async move {
+					current_user.await.ok().flatten().filter(|user| user.is_admin).map(|_| view! {
+						<button
+							class="refresh-button"
+							on:click=move |_| { refresh.dispatch(TriggerRefresh {}); }
+							disabled=move || refresh.pending().get()
+							aria-label="Refresh now"
+						>
+							"⟳ Refresh now"
+						</button>
+					})
+				})}
+			</Suspense>
+		</div>
+	}
+}
+
+fn summary(fetch: &WorkerRunStatus, analysis: &WorkerRunStatus) -> String {
+	if fetch.running {
+		return "Fetching stories…".to_string();
+	}
+	if analysis.running {
+		return "Analyzing articles…".to_string();
+	}
+
+	match (fetch.last_run_secs_ago, fetch.next_run_in_secs) {
+		(Some(ago), Some(next)) => format!("Updated {} ago · next in {}", format_secs(ago), format_secs(next)),
+		(Some(ago), None) => format!("Updated {} ago", format_secs(ago)),
+		_ => "Waiting for first fetch".to_string(),
+	}
+}
+
+fn run_details(label: &str, run: &WorkerRunStatus) -> String {
+	match (run.last_run_secs_ago, run.last_duration_ms) {
+		(Some(ago), Some(ms)) => format!("{}: {} ago in {:.1}s, {} processed, {} failed", label, format_secs(ago), ms as f64 / 1000.0, run.processed, run.failed),
+		_ => format!("{}: not run yet", label),
+	}
+}
+
+fn format_secs(secs: u64) -> String {
+	match secs {
+		0..=59 => format!("{}s", secs),
+		60..=3599 => format!("{} min", secs / 60),
+		_ => format!("{}h{:02}", secs / 3600, (secs % 3600) / 60),
+	}
+}
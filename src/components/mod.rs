@@ -1,4 +1,13 @@
+pub mod analysis_editor;
+pub mod analyzer_status;
 pub mod article_card;
 pub mod article_list;
+pub mod cycle_status;
+pub mod persona_switcher;
+pub mod run_history;
+pub mod saved_list;
 pub mod sort_controls;
+pub mod sparkline;
 pub mod theme_toggle;
+pub mod unread_badge;
+pub mod user_menu;
@@ -0,0 +1,49 @@
+use crate::db::models::{CurrentUser, DEFAULT_PERSONA};
+use crate::server_fns::personas::{get_personas, SetPersona};
+use leptos::prelude::*;
+
+// Only shown once more than the default persona exists; an empty selection means the user's saved persona
+#[component]
+pub fn PersonaSwitcher(selected_persona: Signal<String>, set_selected_persona: WriteSignal<String>, current_user: Resource<Result<Option<CurrentUser>, ServerFnError>>) -> impl IntoView {
+	let personas = Resource::new(|| (), |_| get_personas());
+	let set_persona = ServerAction::<SetPersona>::new();
+
+	view! {
+		<Suspense fallback=|| ()>
+			{move || Suspend::new(async move {
+				let saved = current_user.await.ok().flatten().and_then(|user| user.persona).unwrap_or_else(|| DEFAULT_PERSONA.to_string());
+				match personas.await {
+					Ok(personas) if personas.len() > 1 => view! {
+						<select
+							class="persona-switcher"
+							aria-label="Persona"
+							on:change=move |ev| {
+								let persona = event_target_value(&ev);
+								set_persona.dispatch(SetPersona { persona: persona.clone() });
+								set_selected_persona.set(persona);
+							}
+						>
+							{personas
+								.into_iter()
+								.map(|persona| {
+									let value = persona.name.clone();
+									let is_selected = {
+										let name = persona.name.clone();
+										let saved = saved.clone();
+										move || selected_persona.with(|selected| if selected.is_empty() { name == saved } else { *selected == name })
+									};
+									view! {
+										<option value=value title=persona.description selected=is_selected>
+											{persona.name}
+										</option>
+									}
+								})
+								.collect_view()}
+						</select>
+					}.into_any(),
+					_ => ().into_any(),
+				}
+			})}
+		</Suspense>
+	}
+}
@@ -0,0 +1,63 @@
+use crate::db::models::AggregatorRun;
+use leptos::prelude::*;
+
+#[component]
+pub fn RunHistory(runs: Vec<AggregatorRun>) -> impl IntoView {
+	if runs.is_empty() {
+		return view! {
+			<div class="empty-state">
+				<p>"No aggregator runs recorded yet."</p>
+			</div>
+		}
+		.into_any();
+	}
+
+	view! {
+		<div class="run-history">
+			<table>
+				<thead>
+					<tr>
+						<th>"Started (UTC)"</th>
+						<th>"Worker"</th>
+						<th>"Duration"</th>
+						<th>"Fetched"</th>
+						<th>"New"</th>
+						<th>"Updated"</th>
+						<th>"Analyzed"</th>
+						<th>"Relevant"</th>
+						<th>"Rejected"</th>
+						<th>"LLM errors"</th>
+						<th>"Avg LLM latency"</th>
+					</tr>
+				</thead>
+				<tbody>
+					{runs.into_iter().map(|run| {
+						let is_fetch = run.kind == "fetch";
+						let row_class = if run.error.is_some() { "failed" } else if run.idle { "idle" } else { "" };
+						let dash = || "–".to_string();
+
+						view! {
+							<tr class=row_class title=run.error.clone().unwrap_or_default()>
+								<td>{run.started_at.clone()}</td>
+								<td>{run.kind.clone()}</td>
+								<td>{match (&run.finished_at, run.duration_ms) {
+									(Some(_), Some(ms)) => format!("{:.1}s", ms as f64 / 1000.0),
+									_ => "running".to_string(),
+								}}</td>
+								<td>{if is_fetch { run.stories_fetched.to_string() } else { dash() }}</td>
+								<td>{if is_fetch { run.new_stories.to_string() } else { dash() }}</td>
+								<td>{if is_fetch { run.updated_stories.to_string() } else { dash() }}</td>
+								<td>{if is_fetch { dash() } else { run.analyzed.to_string() }}</td>
+								<td>{if is_fetch { dash() } else { (run.analyzed - run.rejected).to_string() }}</td>
+								<td>{if is_fetch { dash() } else { run.rejected.to_string() }}</td>
+								<td>{if is_fetch { dash() } else { run.llm_errors.to_string() }}</td>
+								<td>{run.avg_llm_latency_ms.map(|ms| format!("{} ms", ms)).unwrap_or_else(dash)}</td>
+							</tr>
+						}
+					}).collect_view()}
+				</tbody>
+			</table>
+		</div>
+	}
+	.into_any()
+}
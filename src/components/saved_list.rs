@@ -0,0 +1,93 @@
+use crate::components::article_card::ArticleCard;
+use crate::db::models::SavedArticle;
+use crate::server_fns::{
+	articles::{MarkArticlesRead, OverrideAnalysis, ResetAnalysis},
+	bookmarks::{AddBookmark, MAX_NOTE_CHARS},
+};
+use leptos::prelude::*;
+
+#[component]
+pub fn SavedList(bookmarks: Vec<SavedArticle>, mark_read: ServerAction<MarkArticlesRead>, override_analysis: ServerAction<OverrideAnalysis>, reset_analysis: ServerAction<ResetAnalysis>, can_edit: Signal<bool>) -> impl IntoView {
+	if bookmarks.is_empty() {
+		return view! {
+			<div class="empty-state">
+				<p>"Nothing saved yet. Use ☆ Save on an article to add it to your reading list."</p>
+			</div>
+		}
+		.into_any();
+	}
+
+	let mut all_tags: Vec<String> = bookmarks.iter().flat_map(|b| b.tags.clone()).collect();
+	all_tags.sort();
+	all_tags.dedup();
+
+	// Filtered client-side, the whole list is already loaded; feedback goes to the reader's saved persona
+	let (selected_tag, set_selected_tag) = signal(None::<String>);
+
+	view! {
+		{(!all_tags.is_empty()).then(|| view! {
+			<div class="tag-filter">
+				<button class="tag" class:active=move || selected_tag.get().is_none() on:click=move |_| set_selected_tag.set(None)>"All"</button>
+				{all_tags
+					.into_iter()
+					.map(|tag| {
+						let is_active = {
+							let tag = tag.clone();
+							move || selected_tag.get().as_ref() == Some(&tag)
+						};
+						let label = format!("#{}", tag);
+						view! {
+							<button class="tag" class:active=is_active on:click=move |_| set_selected_tag.set(Some(tag.clone()))>{label}</button>
+						}
+					})
+					.collect_view()}
+			</div>
+		})}
+		<div class="article-list saved-list">
+			{bookmarks
+				.into_iter()
+				.map(|bookmark| {
+					let tags = bookmark.tags.clone();
+					let is_visible = move || selected_tag.with(|selected| selected.as_ref().is_none_or(|tag| tags.contains(tag)));
+					view! {
+						<div class="saved-entry" class:hidden=move || !is_visible()>
+							<BookmarkEditor article_id=bookmark.article.id note=bookmark.note tags=bookmark.tags saved_at=bookmark.saved_at />
+							<ArticleCard
+								article=bookmark.article
+								persona=Signal::stored(String::new())
+								mark_read=mark_read
+								override_analysis=override_analysis
+								reset_analysis=reset_analysis
+								can_edit=can_edit
+							/>
+						</div>
+					}
+				})
+				.collect_view()}
+		</div>
+	}
+	.into_any()
+}
+
+#[component]
+fn BookmarkEditor(article_id: i64, note: String, tags: Vec<String>, saved_at: String) -> impl IntoView {
+	let save = ServerAction::<AddBookmark>::new();
+
+	view! {
+		<ActionForm action=save attr:class="bookmark-editor">
+			<input type="hidden" name="article_id" value=article_id />
+			<textarea name="note" placeholder="Personal note" rows="2" maxlength=MAX_NOTE_CHARS>{note}</textarea>
+			<div class="bookmark-editor-row">
+				<input type="text" name="tags" placeholder="Tags, comma separated" value=tags.join(", ") />
+				<button type="submit" disabled=move || save.pending().get()>"Save note"</button>
+				<span class="saved-at">
+					{move || match save.value().get() {
+						Some(Ok(())) => "Note saved".to_string(),
+						Some(Err(e)) => format!("Failed to save: {}", e),
+						None => format!("Saved {}", saved_at),
+					}}
+				</span>
+			</div>
+		</ActionForm>
+	}
+}
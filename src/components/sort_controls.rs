@@ -1,17 +1,24 @@
-use crate::db::models::{SortDirection, SortField};
+use crate::db::models::{HnFeed, SortDirection, SortField};
 use crate::server_fns::articles::get_categories_with_counts;
 use leptos::prelude::*;
 
 #[component]
 pub fn SortControls(
+	persona: Signal<String>,
 	sort_field: Signal<SortField>,
 	set_sort_field: WriteSignal<SortField>,
 	sort_direction: Signal<SortDirection>,
 	set_sort_direction: WriteSignal<SortDirection>,
 	selected_category: Signal<String>,
 	set_selected_category: WriteSignal<String>,
+	selected_feed: Signal<Option<HnFeed>>,
+	set_selected_feed: WriteSignal<Option<HnFeed>>,
+	hide_read: Signal<bool>,
+	set_hide_read: WriteSignal<bool>,
+	show_rejected: Signal<bool>,
+	set_show_rejected: WriteSignal<bool>,
 ) -> impl IntoView {
-	let categories_resource = Resource::new(|| (), |_| get_categories_with_counts());
+	let categories_resource = Resource::new(move || (persona.get(), show_rejected.get()), |(persona, rejected)| get_categories_with_counts(persona, rejected));
 
 	view! {
 		<div class="sort-controls">
@@ -24,6 +31,7 @@ pub fn SortControls(
 					let field = match value.as_str() {
 						"score" => SortField::Score,
 						"priority" => SortField::Priority,
+						"velocity" => SortField::Velocity,
 						_ => SortField::Date,
 					};
 					set_sort_field.set(field);
@@ -32,6 +40,7 @@ pub fn SortControls(
 				<option value="date" selected={move || sort_field.get() == SortField::Date}>"Date"</option>
 				<option value="score" selected={move || sort_field.get() == SortField::Score}>"Score"</option>
 				<option value="priority" selected={move || sort_field.get() == SortField::Priority}>"Priority"</option>
+				<option value="velocity" selected={move || sort_field.get() == SortField::Velocity}>"Rising (points/hour)"</option>
 			</select>
 
 			<select
@@ -50,6 +59,7 @@ pub fn SortControls(
 						SortField::Date => "Newest first",
 						SortField::Score => "Highest first",
 						SortField::Priority => "Highest first",
+						SortField::Velocity => "Fastest first",
 					}}
 				</option>
 				<option value="asc" selected={move || sort_direction.get() == SortDirection::Ascending}>
@@ -57,6 +67,7 @@ pub fn SortControls(
 						SortField::Date => "Oldest first",
 						SortField::Score => "Lowest first",
 						SortField::Priority => "Lowest first",
+						SortField::Velocity => "Slowest first",
 					}}
 				</option>
 			</select>
@@ -92,6 +103,30 @@ pub fn SortControls(
 					})}
 				</Suspense>
 			</select>
+
+			<select
+				id="feed-field"
+				class="sort-select"
+				on:change=move |ev| {set_selected_feed.set(HnFeed::parse(&event_target_value(&ev)));}
+			>
+				<option value="" selected=move || selected_feed.get().is_none()>"All feeds"</option>
+				{HnFeed::ALL
+					.into_iter()
+					.map(|feed| view! {
+						<option value=feed.as_str() selected=move || selected_feed.get() == Some(feed)>{feed.label()}</option>
+					})
+					.collect_view()}
+			</select>
+
+			<label class="toggle" title="List what the analyzer filtered out, with its reasons">
+				<input type="checkbox" prop:checked=move || show_rejected.get() on:change=move |ev| set_show_rejected.set(event_target_checked(&ev)) />
+				"Show rejected"
+			</label>
+
+			<label class="toggle">
+				<input type="checkbox" prop:checked=move || hide_read.get() on:change=move |ev| set_hide_read.set(event_target_checked(&ev)) />
+				"Hide read"
+			</label>
 		</div>
 	}
 }
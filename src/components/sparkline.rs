@@ -0,0 +1,34 @@
+use leptos::prelude::*;
+
+const WIDTH: f64 = 80.0;
+const HEIGHT: f64 = 20.0;
+
+// Tiny inline SVG line chart, hidden until there are at least two points
+#[component]
+pub fn Sparkline(values: Vec<i64>) -> impl IntoView {
+	if values.len() < 2 {
+		return ().into_any();
+	}
+
+	let min = *values.iter().min().unwrap_or(&0) as f64;
+	let max = *values.iter().max().unwrap_or(&0) as f64;
+	let range = (max - min).max(1.0);
+	let step = WIDTH / (values.len() - 1) as f64;
+
+	// SVG y grows downwards, keep a 1px margin so the stroke isn't clipped
+	let points = values
+		.iter()
+		.enumerate()
+		.map(|(i, v)| format!("{:.1},{:.1}", i as f64 * step, 1.0 + (HEIGHT - 2.0) * (1.0 - (*v as f64 - min) / range)))
+		.collect::<Vec<_>>()
+		.join(" ");
+	let title = format!("Score over the last {} fetches: {} → {}", values.len(), values[0], values[values.len() - 1]);
+
+	view! {
+		<svg class="sparkline" width=WIDTH height=HEIGHT viewBox=format!("0 0 {} {}", WIDTH, HEIGHT) role="img">
+			<title>{title}</title>
+			<polyline points=points fill="none" stroke="currentColor" stroke-width="1.5" stroke-linejoin="round" stroke-linecap="round" />
+		</svg>
+	}
+	.into_any()
+}
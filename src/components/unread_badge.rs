@@ -0,0 +1,44 @@
+use crate::db::models::{HnFeed, LISTING_LIMIT};
+use crate::server_fns::articles::MarkAllRead;
+use leptos::prelude::*;
+
+#[component]
+pub fn UnreadBadge(
+	persona: Signal<String>,
+	show_rejected: Signal<bool>,
+	selected_category: Signal<String>,
+	selected_feed: Signal<Option<HnFeed>>,
+	unread: Resource<Result<i64, ServerFnError>>,
+	mark_all_read: ServerAction<MarkAllRead>,
+) -> impl IntoView {
+	view! {
+		<Suspense fallback=|| ()>
+			{move || Suspend::new(async move {
+				match unread.await {
+					Ok(count) if count > 0 => view! {
+						<span class="unread">
+							<span class="unread-badge" title="Unread articles under the current filters">
+								{if count > LISTING_LIMIT { format!("{}+", LISTING_LIMIT) } else { count.to_string() }} " unread"
+							</span>
+							<button
+								class="mark-all-read"
+								disabled=move || mark_all_read.pending().get()
+								on:click=move |_| {
+									mark_all_read.dispatch(MarkAllRead {
+										persona: persona.get_untracked(),
+										rejected: show_rejected.get_untracked(),
+										category: selected_category.get_untracked(),
+										feed: selected_feed.get_untracked(),
+									});
+								}
+							>
+								"Mark all read"
+							</button>
+						</span>
+					}.into_any(),
+					_ => ().into_any(),
+				}
+			})}
+		</Suspense>
+	}
+}
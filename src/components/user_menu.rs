@@ -0,0 +1,26 @@
+use crate::db::models::CurrentUser;
+use crate::server_fns::auth::Logout;
+use leptos::prelude::*;
+use leptos::server_fn::ServerFn;
+
+#[component]
+pub fn UserMenu(current_user: Resource<Result<Option<CurrentUser>, ServerFnError>>) -> impl IntoView {
+	view! {
+		<Suspense fallback=|| ()>
+			{move || Suspend::new(async move {
+				match current_user.await {
+					// A plain form post so the whole page reloads without the session
+					Ok(Some(user)) => view! {
+						<form class="user-menu" method="post" action=Logout::url()>
+							<span class="user-name">{format!("👤 {}", user.username)}</span>
+							<button type="submit" class="logout-button">"Log out"</button>
+						</form>
+					}.into_any(),
+					_ => view! {
+						<a class="nav-link" href="/login">"Log in"</a>
+					}.into_any(),
+				}
+			})}
+		</Suspense>
+	}
+}
@@ -1,16 +1,89 @@
 #[cfg(feature = "ssr")]
 use anyhow::Result;
 
+#[cfg(feature = "ssr")]
+use crate::db::models::{HnFeed, DEFAULT_PERSONA};
+
+#[cfg(feature = "ssr")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnalyzerProvider {
+	Ollama,
+	OpenAi,
+	RuleBased,
+}
+
+#[cfg(feature = "ssr")]
+impl AnalyzerProvider {
+	fn from_env_value(value: &str) -> Option<Self> {
+		match value.trim().to_ascii_lowercase().as_str() {
+			"ollama" => Some(Self::Ollama),
+			"openai" => Some(Self::OpenAi),
+			"rules" | "rule-based" => Some(Self::RuleBased),
+			_ => None,
+		}
+	}
+}
+
+// Named interest profile, synced into the personas table at startup
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PersonaConfig {
+	pub name: String,
+	pub description: String,
+}
+
+// One HN feed to poll and how many of its stories to take
+#[cfg(feature = "ssr")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedConfig {
+	pub feed: HnFeed,
+	pub limit: usize,
+}
+
 #[cfg(feature = "ssr")]
 #[derive(Clone, Debug)]
 pub struct Config {
 	pub database_url: String,
+	pub hn_api_base: String,
+	pub hn_max_concurrent_requests: usize,
+	pub hn_requests_per_second: f64,
+	pub retry_max_attempts: u32,
+	pub retry_base_delay_ms: u64,
+	pub retry_max_delay_ms: u64,
+	pub breaker_failure_threshold: u32,
+	pub breaker_cooldown_secs: u64,
+	pub analyzer_provider: AnalyzerProvider,
 	pub ollama_url: String,
 	pub ollama_model: String,
-	pub persona: String,
+	pub openai_url: String,
+	pub openai_model: String,
+	pub openai_api_key: Option<String>,
+	// "default" (persona.txt) first, then one per file in the personas directory
+	pub personas: Vec<PersonaConfig>,
+	// How far back a new persona's analysis queue is filled
+	pub persona_backfill_days: i64,
 	pub categories: Vec<String>,
 	pub fetch_interval_minutes: u64,
+	pub analysis_interval_minutes: u64,
+	pub shutdown_timeout_secs: u64,
 	pub top_stories_count: usize,
+	pub feeds: Vec<FeedConfig>,
+	pub exclude_job_posts: bool,
+	pub analysis_max_attempts: i64,
+	pub analysis_retry_delay_minutes: i64,
+	pub fetch_article_content: bool,
+	pub content_excerpt_chars: usize,
+	pub comment_summary_min_priority: i64,
+	pub comment_summary_min_comments: i64,
+	pub comment_summary_max_comments: usize,
+	// Thumbs up and thumbs down examples each, added to the analysis prompt
+	pub feedback_examples: i64,
+	pub auth_required: bool,
+	pub registration_enabled: bool,
+	pub session_ttl_days: u64,
+	pub session_cookie_secure: bool,
+	// Bearer token /metrics requires instead of a session
+	pub metrics_token: Option<String>,
 }
 
 #[cfg(feature = "ssr")]
@@ -29,6 +102,15 @@ impl Config {
 			}
 		};
 
+		let mut personas = vec![PersonaConfig { name: DEFAULT_PERSONA.to_string(), description: persona }];
+		let personas_dir = match std::env::var("PERSONAS_DIR") {
+			Ok(dir) => Some(dir),
+			Err(_) => ["personas", "config/personas"].into_iter().find(|dir| std::path::Path::new(dir).is_dir()).map(str::to_string),
+		};
+		if let Some(dir) = personas_dir {
+			personas.extend(load_personas(&dir).await?);
+		}
+
 		let categories_text = match tokio::fs::read_to_string("categories.txt").await {
 			Ok(content) => content,
 			Err(_) => match tokio::fs::read_to_string("config/categories.txt").await {
@@ -51,20 +133,210 @@ impl Config {
 			categories.push("Other".to_string());
 		}
 
+		let analyzer_provider = match std::env::var("ANALYZER_PROVIDER") {
+			Ok(value) => AnalyzerProvider::from_env_value(&value).ok_or_else(|| anyhow::anyhow!("Unknown ANALYZER_PROVIDER '{}' (expected ollama, openai or rules)", value))?,
+			Err(_) => AnalyzerProvider::Ollama,
+		};
+
+		let top_stories_count = std::env::var("TOP_STORIES_COUNT").ok().and_then(|s| s.parse().ok()).unwrap_or(15);
+
+		// HN_FEEDS looks like "top:30,show:10,ask:5"; without it only the top feed is polled
+		let feeds = match std::env::var("HN_FEEDS") {
+			Ok(value) => parse_feeds(&value, top_stories_count)?,
+			Err(_) => vec![FeedConfig { feed: HnFeed::Top, limit: top_stories_count }],
+		};
+
+		let auth_required = std::env::var("AUTH_REQUIRED")
+			.ok()
+			.and_then(|s| s.parse().ok())
+			.unwrap_or(false);
+
 		Ok(Self {
 			database_url: std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:articles.db".to_string()),
+			hn_api_base: std::env::var("HN_API_BASE").unwrap_or_else(|_| "https://hacker-news.firebaseio.com/v0".to_string()),
+			hn_max_concurrent_requests: std::env::var("HN_MAX_CONCURRENT_REQUESTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(8),
+			hn_requests_per_second: std::env::var("HN_REQUESTS_PER_SECOND")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(20.0),
+			retry_max_attempts: std::env::var("RETRY_MAX_ATTEMPTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(3),
+			retry_base_delay_ms: std::env::var("RETRY_BASE_DELAY_MS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(500),
+			retry_max_delay_ms: std::env::var("RETRY_MAX_DELAY_MS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(10_000),
+			breaker_failure_threshold: std::env::var("BREAKER_FAILURE_THRESHOLD")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(5),
+			breaker_cooldown_secs: std::env::var("BREAKER_COOLDOWN_SECS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(300),
+			analyzer_provider,
 			ollama_url: std::env::var("OLLAMA_URL").unwrap_or_else(|_| "http://localhost:11434".to_string()),
 			ollama_model: std::env::var("OLLAMA_MODEL").unwrap_or_else(|_| "qwen2.5:7b".to_string()),
-			persona,
+			openai_url: std::env::var("OPENAI_URL").unwrap_or_else(|_| "http://localhost:8080".to_string()),
+			openai_model: std::env::var("OPENAI_MODEL").unwrap_or_else(|_| "default".to_string()),
+			openai_api_key: std::env::var("OPENAI_API_KEY").ok().filter(|s| !s.is_empty()),
+			personas,
+			persona_backfill_days: std::env::var("PERSONA_BACKFILL_DAYS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(7),
 			categories,
 			fetch_interval_minutes: std::env::var("FETCH_INTERVAL_MINUTES")
 				.ok()
 				.and_then(|s| s.parse().ok())
 				.unwrap_or(60),
-			top_stories_count: std::env::var("TOP_STORIES_COUNT")
+			analysis_interval_minutes: std::env::var("ANALYSIS_INTERVAL_MINUTES")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(5),
+			shutdown_timeout_secs: std::env::var("SHUTDOWN_TIMEOUT_SECS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(60),
+			top_stories_count,
+			feeds,
+			exclude_job_posts: std::env::var("EXCLUDE_JOB_POSTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(false),
+			analysis_max_attempts: std::env::var("ANALYSIS_MAX_ATTEMPTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(5),
+			analysis_retry_delay_minutes: std::env::var("ANALYSIS_RETRY_DELAY_MINUTES")
 				.ok()
 				.and_then(|s| s.parse().ok())
 				.unwrap_or(15),
+			fetch_article_content: std::env::var("FETCH_ARTICLE_CONTENT")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(true),
+			content_excerpt_chars: std::env::var("CONTENT_EXCERPT_CHARS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(2000),
+			comment_summary_min_priority: std::env::var("COMMENT_SUMMARY_MIN_PRIORITY")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(4),
+			comment_summary_min_comments: std::env::var("COMMENT_SUMMARY_MIN_COMMENTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(10),
+			comment_summary_max_comments: std::env::var("COMMENT_SUMMARY_MAX_COMMENTS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(20),
+			feedback_examples: std::env::var("FEEDBACK_EXAMPLES")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(5),
+			auth_required,
+			// A private instance shouldn't let anyone sign themselves up unless asked to
+			registration_enabled: std::env::var("REGISTRATION_ENABLED")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(!auth_required),
+			session_ttl_days: std::env::var("SESSION_TTL_DAYS")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(30),
+			session_cookie_secure: std::env::var("SESSION_COOKIE_SECURE")
+				.ok()
+				.and_then(|s| s.parse().ok())
+				.unwrap_or(false),
+			metrics_token: std::env::var("METRICS_TOKEN").ok().filter(|s| !s.is_empty()),
 		})
 	}
 }
+
+// Every `<name>.txt` in `dir` becomes a persona called `<name>`
+#[cfg(feature = "ssr")]
+async fn load_personas(dir: &str) -> Result<Vec<PersonaConfig>> {
+	let mut personas = Vec::new();
+	let mut entries = tokio::fs::read_dir(dir).await.map_err(|e| anyhow::anyhow!("Failed to read personas directory '{}': {}", dir, e))?;
+
+	while let Some(entry) = entries.next_entry().await? {
+		let path = entry.path();
+		if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+			continue;
+		}
+		let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) else {
+			continue;
+		};
+		if name == DEFAULT_PERSONA {
+			tracing::warn!("Ignoring {}, the default persona comes from persona.txt", path.display());
+			continue;
+		}
+
+		let description = tokio::fs::read_to_string(&path).await?;
+		personas.push(PersonaConfig { name, description });
+	}
+
+	// read_dir order is platform dependent, keep the switcher stable
+	personas.sort_by(|a, b| a.name.cmp(&b.name));
+	tracing::info!("Loaded {} extra personas from {}", personas.len(), dir);
+
+	Ok(personas)
+}
+
+#[cfg(feature = "ssr")]
+fn parse_feeds(value: &str, default_limit: usize) -> Result<Vec<FeedConfig>> {
+	let mut feeds: Vec<FeedConfig> = Vec::new();
+
+	for entry in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+		let (name, limit) = match entry.split_once(':') {
+			Some((name, limit)) => (name, limit.trim().parse().map_err(|_| anyhow::anyhow!("Invalid limit in HN_FEEDS entry '{}'", entry))?),
+			None => (entry, default_limit),
+		};
+		let feed = HnFeed::parse(name).ok_or_else(|| anyhow::anyhow!("Unknown feed '{}' in HN_FEEDS (expected top, new, best, ask, show or job)", name))?;
+
+		match feeds.iter_mut().find(|f| f.feed == feed) {
+			Some(existing) => existing.limit = limit,
+			None => feeds.push(FeedConfig { feed, limit }),
+		}
+	}
+
+	Ok(feeds)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_feeds_reads_names_and_limits() {
+		let feeds = parse_feeds("top, ask:10 ,SHOW:5,", 30).unwrap();
+		assert_eq!(feeds, vec![FeedConfig { feed: HnFeed::Top, limit: 30 }, FeedConfig { feed: HnFeed::Ask, limit: 10 }, FeedConfig { feed: HnFeed::Show, limit: 5 }]);
+	}
+
+	#[test]
+	fn parse_feeds_keeps_the_last_limit_of_a_repeated_feed() {
+		assert_eq!(parse_feeds("new:5,best,new:20", 30).unwrap(), vec![FeedConfig { feed: HnFeed::New, limit: 20 }, FeedConfig { feed: HnFeed::Best, limit: 30 }]);
+	}
+
+	#[test]
+	fn parse_feeds_rejects_unknown_feeds_and_bad_limits() {
+		assert!(parse_feeds("top,front", 30).is_err());
+		assert!(parse_feeds("top:many", 30).is_err());
+		assert!(parse_feeds("top:-1", 30).is_err());
+	}
+
+	#[test]
+	fn parse_feeds_allows_an_empty_list() {
+		assert!(parse_feeds(" , ", 30).unwrap().is_empty());
+	}
+}
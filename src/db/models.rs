@@ -9,11 +9,141 @@ pub struct Article {
 	pub score: i64,
 	pub timestamp: i64,
 	pub fetched_at: String,
+	// Analysis for the persona the article was loaded for
+	#[cfg_attr(feature = "ssr", sqlx(default))]
 	pub ai_analysis_done: bool,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
 	pub is_interesting: bool,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
 	pub reason: Option<String>,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
 	pub priority: Option<i64>,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
 	pub category: Option<String>,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub summary: Option<String>,
+	#[cfg_attr(feature = "ssr", sqlx(json))]
+	pub key_points: Vec<String>,
+	pub feed: String,
+	pub author: Option<String>,
+	pub descendants: Option<i64>,
+	pub item_type: String,
+	pub dead: bool,
+	pub deleted: bool,
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub discussion_summary: Option<String>,
+	// Relevance, priority and category were set by hand for this persona
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub overridden: bool,
+	// Raw HN body (Ask/Show HN) and top-level comment ids, server-side only
+	#[serde(skip)]
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub text: Option<String>,
+	#[serde(skip)]
+	#[cfg_attr(feature = "ssr", sqlx(json))]
+	pub kids: Vec<i64>,
+	// Extracted page text, only loaded for analysis and never sent to the client
+	#[serde(skip)]
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub content: Option<String>,
+	// Points per hour since posting, only computed by the article listing
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub velocity: f64,
+	// Opened or marked read by the current reader, only computed by the article listing
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub is_read: bool,
+	// On the current reader's reading list, only computed by the listings
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub is_bookmarked: bool,
+	// Current reader's relevance vote for the persona: 1, -1, or 0 when none
+	#[cfg_attr(feature = "ssr", sqlx(default))]
+	pub feedback: i64,
+	// Scores captured at each fetch, oldest first, for the sparkline
+	#[serde(default)]
+	#[cfg_attr(feature = "ssr", sqlx(skip))]
+	pub score_history: Vec<i64>,
+}
+
+impl Article {
+	pub fn discussion_url(&self) -> String {
+		format!("https://news.ycombinator.com/item?id={}", self.hn_id)
+	}
+}
+
+// Persona synced from persona.txt, always present
+pub const DEFAULT_PERSONA: &str = "default";
+
+// Articles shown per listing, the unread badge counts up to this many
+pub const LISTING_LIMIT: i64 = 50;
+
+// Named interest profile articles are analyzed against
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Persona {
+	pub id: i64,
+	pub name: String,
+	pub description: String,
+}
+
+// Signed-in user, resolved from the session cookie on every request
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct CurrentUser {
+	pub id: i64,
+	pub username: String,
+	// Preferred persona name, None for the default one
+	pub persona: Option<String>,
+	// May trigger fetches and re-analysis
+	pub is_admin: bool,
+}
+
+// Reading list entry with the reader's note and tags
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SavedArticle {
+	#[cfg_attr(feature = "ssr", sqlx(flatten))]
+	pub article: Article,
+	pub note: String,
+	#[cfg_attr(feature = "ssr", sqlx(json))]
+	pub tags: Vec<String>,
+	pub saved_at: String,
+}
+
+// Aggregator run history row
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct AggregatorRun {
+	pub id: i64,
+	pub kind: String,
+	pub started_at: String,
+	pub finished_at: Option<String>,
+	pub duration_ms: Option<i64>,
+	pub stories_fetched: i64,
+	pub new_stories: i64,
+	pub updated_stories: i64,
+	pub analyzed: i64,
+	pub rejected: i64,
+	pub llm_errors: i64,
+	pub avg_llm_latency_ms: Option<i64>,
+	pub error: Option<String>,
+	// Analysis cycle with nothing to analyze or summarize
+	pub idle: bool,
+}
+
+// Counters recorded when a run finishes (server-side only)
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Default)]
+pub struct RunMetrics {
+	pub duration_ms: i64,
+	pub stories_fetched: i64,
+	pub new_stories: i64,
+	pub updated_stories: i64,
+	pub analyzed: i64,
+	pub rejected: i64,
+	pub llm_errors: i64,
+	pub avg_llm_latency_ms: Option<i64>,
+	pub error: Option<String>,
+	pub idle: bool,
 }
 
 // HN API response models (server-side only)
@@ -28,6 +158,67 @@ pub struct HnItem {
 	#[serde(default)]
 	pub score: Option<i64>,
 	pub time: i64,
+	#[serde(default)]
+	pub text: Option<String>,
+	#[serde(default)]
+	pub descendants: Option<i64>,
+	#[serde(default)]
+	pub by: Option<String>,
+	#[serde(default, rename = "type")]
+	pub item_type: Option<String>,
+	#[serde(default)]
+	pub kids: Vec<i64>,
+	#[serde(default)]
+	pub dead: bool,
+	#[serde(default)]
+	pub deleted: bool,
+}
+
+// Analysis queue job states (server-side only)
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+	Pending,
+	Running,
+	Failed,
+	Done,
+	Dead,
+}
+
+#[cfg(feature = "ssr")]
+impl JobStatus {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			JobStatus::Pending => "pending",
+			JobStatus::Running => "running",
+			JobStatus::Failed => "failed",
+			JobStatus::Done => "done",
+			JobStatus::Dead => "dead",
+		}
+	}
+}
+
+// Article waiting in the analysis queue, with its job bookkeeping (server-side only)
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingAnalysis {
+	#[sqlx(flatten)]
+	pub article: Article,
+	pub job_id: i64,
+	pub attempts: i64,
+	pub persona_id: i64,
+	pub persona_name: String,
+	// Persona description the article is analyzed against
+	pub persona: String,
+}
+
+// Article a reader voted on, used as a few-shot example in the analysis prompt (server-side only)
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeedbackExample {
+	pub article_id: i64,
+	pub title: String,
+	pub relevant: bool,
 }
 
 // Ollama analysis result (server-side only)
@@ -39,6 +230,10 @@ pub struct AnalysisResult {
 	pub priority: i64,
 	#[serde(default = "default_category")]
 	pub category: String,
+	#[serde(default)]
+	pub summary: Option<String>,
+	#[serde(default)]
+	pub key_points: Vec<String>,
 }
 
 #[cfg(feature = "ssr")]
@@ -52,28 +247,94 @@ pub struct CategoryCount {
 	pub count: i32,
 }
 
+// HN story feeds (shared between client and server)
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum HnFeed {
+	Top,
+	New,
+	Best,
+	Ask,
+	Show,
+	Job,
+}
+
+impl HnFeed {
+	pub const ALL: [HnFeed; 6] = [HnFeed::Top, HnFeed::New, HnFeed::Best, HnFeed::Ask, HnFeed::Show, HnFeed::Job];
+
+	// Identifier used in the database, config and HN endpoint names
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			HnFeed::Top => "top",
+			HnFeed::New => "new",
+			HnFeed::Best => "best",
+			HnFeed::Ask => "ask",
+			HnFeed::Show => "show",
+			HnFeed::Job => "job",
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			HnFeed::Top => "Top",
+			HnFeed::New => "New",
+			HnFeed::Best => "Best",
+			HnFeed::Ask => "Ask HN",
+			HnFeed::Show => "Show HN",
+			HnFeed::Job => "Jobs",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|f| f.as_str().eq_ignore_ascii_case(value.trim()))
+	}
+}
+
+// Analyzer circuit breaker state (shared between client and server)
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum BreakerState {
+	Closed,
+	Open,
+	HalfOpen,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct BreakerStatus {
+	pub state: BreakerState,
+	pub consecutive_failures: u32,
+	pub retry_in_secs: Option<u64>,
+}
+
+// Background worker activity (shared between client and server)
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct WorkerRunStatus {
+	pub running: bool,
+	pub last_run_at: Option<i64>,
+	pub last_run_secs_ago: Option<u64>,
+	pub last_duration_ms: Option<u64>,
+	pub processed: usize,
+	pub failed: usize,
+	pub next_run_in_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct AggregatorStatus {
+	pub fetch: WorkerRunStatus,
+	pub analysis: WorkerRunStatus,
+}
+
 // Sorting enums (shared between client and server)
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq)]
 pub enum SortField {
+	#[default]
 	Date,
 	Score,
 	Priority,
+	Velocity,
 }
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq)]
 pub enum SortDirection {
 	Ascending,
+	#[default]
 	Descending,
 }
-
-impl Default for SortField {
-	fn default() -> Self {
-		Self::Date
-	}
-}
-
-impl Default for SortDirection {
-	fn default() -> Self {
-		Self::Descending
-	}
-}
@@ -1,3 +1,6 @@
+#[cfg(feature = "ssr")]
+use std::collections::HashMap;
+
 #[cfg(feature = "ssr")]
 use anyhow::Result;
 
@@ -5,20 +8,46 @@ use anyhow::Result;
 use sqlx::SqlitePool;
 
 #[cfg(feature = "ssr")]
-use crate::db::models::{AnalysisResult, Article, HnItem, SortDirection, SortField};
+use crate::db::models::{AggregatorRun, AnalysisResult, Article, CurrentUser, FeedbackExample, HnFeed, HnItem, JobStatus, LISTING_LIMIT, PendingAnalysis, Persona, RunMetrics, SavedArticle, SortDirection, SortField};
+
+#[cfg(feature = "ssr")]
+use crate::metrics::DbTimer;
+
+// Flags a story HN has since killed or deleted so it drops out of listings and the analysis queue
+#[cfg(feature = "ssr")]
+pub async fn mark_article_removed(pool: &SqlitePool, item: &HnItem) -> Result<()> {
+	let _timer = DbTimer::new("mark_article_removed");
+	sqlx::query("UPDATE articles SET dead = ?, deleted = ? WHERE hn_id = ?").bind(item.dead).bind(item.deleted).bind(item.id).execute(pool).await?;
+
+	Ok(())
+}
 
+// Returns true when the story was not in the database yet
 #[cfg(feature = "ssr")]
-pub async fn upsert_article(pool: &SqlitePool, item: &HnItem) -> Result<()> {
+pub async fn upsert_article(pool: &SqlitePool, item: &HnItem, feed: HnFeed, content: Option<&str>) -> Result<bool> {
+	let _timer = DbTimer::new("upsert_article");
 	let title = item.title.clone().unwrap_or_default();
 	let score = item.score.unwrap_or(0);
 
+	let mut tx = pool.begin().await?;
+
+	let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM articles WHERE hn_id = ?").bind(item.id).fetch_optional(&mut *tx).await?;
+
 	sqlx::query(
 		r#"
-		INSERT INTO articles (hn_id, title, url, score, timestamp)
-		VALUES (?, ?, ?, ?, ?)
+		INSERT INTO articles (hn_id, title, url, score, timestamp, feed, content, author, descendants, item_type, text, kids, dead, deleted)
+		VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 		ON CONFLICT(hn_id) DO UPDATE SET
 			score = excluded.score,
-			title = excluded.title
+			title = excluded.title,
+			content = COALESCE(articles.content, excluded.content),
+			author = excluded.author,
+			descendants = excluded.descendants,
+			item_type = excluded.item_type,
+			text = excluded.text,
+			kids = excluded.kids,
+			dead = excluded.dead,
+			deleted = excluded.deleted
 		"#,
 	)
 	.bind(item.id)
@@ -26,50 +55,442 @@ pub async fn upsert_article(pool: &SqlitePool, item: &HnItem) -> Result<()> {
 	.bind(&item.url)
 	.bind(score)
 	.bind(item.time)
-	.execute(pool)
+	.bind(feed.as_str())
+	.bind(content)
+	.bind(&item.by)
+	.bind(item.descendants)
+	.bind(item.item_type.as_deref().unwrap_or("story"))
+	.bind(&item.text)
+	.bind(serde_json::to_string(&item.kids)?)
+	.bind(item.dead)
+	.bind(item.deleted)
+	.execute(&mut *tx)
 	.await?;
 
-	Ok(())
+	// Keep every fetch's score so the trajectory survives the overwrite above
+	sqlx::query("INSERT INTO score_snapshots (article_id, score, descendants) SELECT id, ?, ? FROM articles WHERE hn_id = ?").bind(score).bind(item.descendants).bind(item.id).execute(&mut *tx).await?;
+
+	// One pending job per active persona; existing jobs are left alone, new personas are backfilled at startup
+	sqlx::query("INSERT OR IGNORE INTO analysis_jobs (article_id, persona_id) SELECT a.id, p.id FROM articles a, personas p WHERE a.hn_id = ? AND p.active = 1").bind(item.id).execute(&mut *tx).await?;
+
+	tx.commit().await?;
+
+	Ok(existing.is_none())
 }
 
+// Job posts stored before EXCLUDE_JOB_POSTS was set are skipped when `exclude_jobs` is
 #[cfg(feature = "ssr")]
-pub async fn get_unanalyzed_articles(pool: &SqlitePool) -> Result<Vec<Article>> {
-	let articles = sqlx::query_as::<_, Article>(
+pub async fn get_due_analysis_jobs(pool: &SqlitePool, exclude_jobs: bool) -> Result<Vec<PendingAnalysis>> {
+	let _timer = DbTimer::new("get_due_analysis_jobs");
+	let jobs = sqlx::query_as::<_, PendingAnalysis>(
 		r#"
-		SELECT id, hn_id, title, url, score, timestamp, fetched_at, ai_analysis_done, is_interesting, reason, priority, category
-		FROM articles
-		WHERE ai_analysis_done = 0
-		ORDER BY fetched_at DESC
+		SELECT a.id, a.hn_id, a.title, a.url, a.score, a.timestamp, a.fetched_at, a.summary, a.key_points, a.feed,
+			a.author, a.descendants, a.item_type, a.text, a.kids, a.dead, a.deleted, a.content,
+			j.id AS job_id, j.attempts, p.id AS persona_id, p.name AS persona_name, p.description AS persona
+		FROM analysis_jobs j
+		JOIN articles a ON a.id = j.article_id
+		JOIN personas p ON p.id = j.persona_id AND p.active = 1
+		WHERE j.status IN ('pending', 'failed') AND j.next_run_at <= CURRENT_TIMESTAMP AND a.dead = 0 AND a.deleted = 0 AND (? = 0 OR a.item_type != 'job')
+		ORDER BY a.fetched_at DESC, p.id
 		"#,
 	)
+	.bind(exclude_jobs)
 	.fetch_all(pool)
 	.await?;
 
-	Ok(articles)
+	Ok(jobs)
+}
+
+// Jobs left running by a crash or restart go back to the queue
+#[cfg(feature = "ssr")]
+pub async fn reset_running_jobs(pool: &SqlitePool) -> Result<u64> {
+	let _timer = DbTimer::new("reset_running_jobs");
+	let result = sqlx::query("UPDATE analysis_jobs SET status = 'pending', updated_at = CURRENT_TIMESTAMP WHERE status = 'running'").execute(pool).await?;
+
+	Ok(result.rows_affected())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn start_analysis_job(pool: &SqlitePool, job_id: i64) -> Result<()> {
+	let _timer = DbTimer::new("start_analysis_job");
+	sqlx::query("UPDATE analysis_jobs SET status = 'running', attempts = attempts + 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?").bind(job_id).execute(pool).await?;
+
+	Ok(())
 }
 
+// Record a failed attempt: retry after `retry_delay_minutes`, or dead-letter once `max_attempts` is reached
 #[cfg(feature = "ssr")]
-pub async fn update_analysis(pool: &SqlitePool, article_id: i64, analysis: AnalysisResult) -> Result<()> {
+pub async fn fail_analysis_job(pool: &SqlitePool, job_id: i64, error: &str, max_attempts: i64, retry_delay_minutes: i64) -> Result<JobStatus> {
+	let _timer = DbTimer::new("fail_analysis_job");
+	let attempts: i64 = sqlx::query_scalar("SELECT attempts FROM analysis_jobs WHERE id = ?").bind(job_id).fetch_one(pool).await?;
+	let status = if attempts >= max_attempts { JobStatus::Dead } else { JobStatus::Failed };
+
 	sqlx::query(
 		r#"
-		UPDATE articles
-		SET ai_analysis_done = 1, is_interesting = ?, reason = ?, priority = ?, category = ?
+		UPDATE analysis_jobs
+		SET status = ?, last_error = ?, next_run_at = datetime('now', ?), updated_at = CURRENT_TIMESTAMP
 		WHERE id = ?
 		"#,
 	)
+	.bind(status.as_str())
+	.bind(error)
+	.bind(format!("+{} minutes", retry_delay_minutes))
+	.bind(job_id)
+	.execute(pool)
+	.await?;
+
+	Ok(status)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn update_content(pool: &SqlitePool, article_id: i64, content: &str) -> Result<()> {
+	let _timer = DbTimer::new("update_content");
+	sqlx::query("UPDATE articles SET content = ? WHERE id = ?").bind(content).bind(article_id).execute(pool).await?;
+
+	Ok(())
+}
+
+// Hand-overridden analyses are kept, the job is still marked done
+#[cfg(feature = "ssr")]
+pub async fn update_analysis(pool: &SqlitePool, article_id: i64, persona_id: i64, stamp: &str, analysis: AnalysisResult) -> Result<()> {
+	let _timer = DbTimer::new("update_analysis");
+	let mut tx = pool.begin().await?;
+
+	sqlx::query(
+		r#"
+		INSERT INTO article_analyses (article_id, persona_id, is_interesting, reason, priority, category, analysis_stamp)
+		VALUES (?, ?, ?, ?, ?, ?, ?)
+		ON CONFLICT(article_id, persona_id) DO UPDATE SET
+			is_interesting = excluded.is_interesting,
+			reason = excluded.reason,
+			priority = excluded.priority,
+			category = excluded.category,
+			analysis_stamp = excluded.analysis_stamp,
+			analyzed_at = CURRENT_TIMESTAMP
+		WHERE NOT article_analyses.overridden
+		"#,
+	)
+	.bind(article_id)
+	.bind(persona_id)
 	.bind(analysis.relevant)
 	.bind(analysis.reason)
 	.bind(analysis.priority)
 	.bind(analysis.category)
+	.bind(stamp)
+	.execute(&mut *tx)
+	.await?;
+
+	// Summary and key points describe the article itself, whichever persona's analysis produced them
+	if analysis.summary.is_some() || !analysis.key_points.is_empty() {
+		sqlx::query("UPDATE articles SET summary = ?, key_points = ? WHERE id = ?").bind(analysis.summary).bind(serde_json::to_string(&analysis.key_points)?).bind(article_id).execute(&mut *tx).await?;
+	}
+
+	sqlx::query("UPDATE analysis_jobs SET status = 'done', last_error = NULL, updated_at = CURRENT_TIMESTAMP WHERE article_id = ? AND persona_id = ?").bind(article_id).bind(persona_id).execute(&mut *tx).await?;
+
+	tx.commit().await?;
+
+	Ok(())
+}
+
+// Returns false when no persona has that name. The analyzer's reason no longer applies and is dropped,
+// the next analysis after clear_override writes a new one
+#[cfg(feature = "ssr")]
+pub async fn override_analysis(pool: &SqlitePool, article_id: i64, persona: &str, user_id: i64, is_interesting: bool, priority: i64, category: &str) -> Result<bool> {
+	let _timer = DbTimer::new("override_analysis");
+	let result = sqlx::query(
+		r#"
+		INSERT INTO article_analyses (article_id, persona_id, is_interesting, priority, category, reason, overridden, overridden_by)
+		SELECT ?, id, ?, ?, ?, NULL, 1, ? FROM personas WHERE name = ?
+		ON CONFLICT(article_id, persona_id) DO UPDATE SET
+			is_interesting = excluded.is_interesting,
+			priority = excluded.priority,
+			category = excluded.category,
+			reason = NULL,
+			overridden = 1,
+			overridden_by = excluded.overridden_by
+		"#,
+	)
 	.bind(article_id)
+	.bind(is_interesting)
+	.bind(priority)
+	.bind(category)
+	.bind(user_id)
+	.bind(persona)
 	.execute(pool)
 	.await?;
 
+	Ok(result.rows_affected() > 0)
+}
+
+// Drops the override and queues the article for a fresh analysis
+#[cfg(feature = "ssr")]
+pub async fn clear_override(pool: &SqlitePool, article_id: i64, persona: &str) -> Result<()> {
+	let _timer = DbTimer::new("clear_override");
+	let mut tx = pool.begin().await?;
+
+	sqlx::query("UPDATE article_analyses SET overridden = 0, overridden_by = NULL WHERE article_id = ? AND persona_id = (SELECT id FROM personas WHERE name = ?)").bind(article_id).bind(persona).execute(&mut *tx).await?;
+	sqlx::query(
+		r#"
+		INSERT INTO analysis_jobs (article_id, persona_id)
+		SELECT ?, id FROM personas WHERE name = ?
+		ON CONFLICT(article_id, persona_id) DO UPDATE SET status = 'pending', attempts = 0, last_error = NULL, next_run_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+		"#,
+	)
+	.bind(article_id)
+	.bind(persona)
+	.execute(&mut *tx)
+	.await?;
+
+	tx.commit().await?;
+
+	Ok(())
+}
+
+// Analyses of the persona made with another stamp, overridden ones excepted; `since_days` limits them to recent stories
+#[cfg(feature = "ssr")]
+const STALE_ANALYSES: &str = r#"
+	SELECT aa.article_id
+	FROM article_analyses aa
+	JOIN articles a ON a.id = aa.article_id
+	WHERE aa.persona_id = ? AND aa.overridden = 0 AND aa.analysis_stamp IS NOT ? AND a.dead = 0 AND a.deleted = 0
+		AND (? IS NULL OR a.timestamp >= unixepoch() - ? * 86400)
+"#;
+
+#[cfg(feature = "ssr")]
+pub async fn count_stale_analyses(pool: &SqlitePool, persona_id: i64, stamp: &str, since_days: Option<i64>) -> Result<i64> {
+	let _timer = DbTimer::new("count_stale_analyses");
+	let count = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({})", STALE_ANALYSES)).bind(persona_id).bind(stamp).bind(since_days).bind(since_days).fetch_one(pool).await?;
+
+	Ok(count)
+}
+
+// Puts stale analyses back in the queue; jobs already pending or running are left as they are
+#[cfg(feature = "ssr")]
+pub async fn requeue_stale_analyses(pool: &SqlitePool, persona_id: i64, stamp: &str, since_days: Option<i64>) -> Result<u64> {
+	let _timer = DbTimer::new("requeue_stale_analyses");
+	let result = sqlx::query(&format!(
+		r#"
+		UPDATE analysis_jobs SET status = 'pending', attempts = 0, last_error = NULL, next_run_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+		WHERE persona_id = ? AND status NOT IN ('pending', 'running') AND article_id IN ({})
+		"#,
+		STALE_ANALYSES
+	))
+	.bind(persona_id)
+	.bind(persona_id)
+	.bind(stamp)
+	.bind(since_days)
+	.bind(since_days)
+	.execute(pool)
+	.await?;
+
+	Ok(result.rows_affected())
+}
+
+// Creates the persona or refreshes its description, returns its id
+#[cfg(feature = "ssr")]
+pub async fn upsert_persona(pool: &SqlitePool, name: &str, description: &str) -> Result<i64> {
+	let _timer = DbTimer::new("upsert_persona");
+	let id = sqlx::query_scalar(
+		r#"
+		INSERT INTO personas (name, description) VALUES (?, ?)
+		ON CONFLICT(name) DO UPDATE SET
+			description = excluded.description,
+			active = 1,
+			updated_at = CASE WHEN personas.description = excluded.description THEN personas.updated_at ELSE CURRENT_TIMESTAMP END
+		RETURNING id
+		"#,
+	)
+	.bind(name)
+	.bind(description)
+	.fetch_one(pool)
+	.await?;
+
+	Ok(id)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_personas(pool: &SqlitePool) -> Result<Vec<Persona>> {
+	let _timer = DbTimer::new("get_personas");
+	let personas = sqlx::query_as::<_, Persona>("SELECT id, name, description FROM personas WHERE active = 1 ORDER BY id").fetch_all(pool).await?;
+
+	Ok(personas)
+}
+
+// Personas missing from `names` stop being analyzed and listed, their data is kept in case they come back
+#[cfg(feature = "ssr")]
+pub async fn deactivate_personas(pool: &SqlitePool, names: &[String]) -> Result<Vec<String>> {
+	let _timer = DbTimer::new("deactivate_personas");
+	let deactivated = sqlx::query_scalar("UPDATE personas SET active = 0, updated_at = CURRENT_TIMESTAMP WHERE active = 1 AND name NOT IN (SELECT value FROM json_each(?)) RETURNING name")
+		.bind(serde_json::to_string(names)?)
+		.fetch_all(pool)
+		.await?;
+
+	Ok(deactivated)
+}
+
+// Queue the persona's analysis of articles posted in the last `since_days` days, for personas created after them
+#[cfg(feature = "ssr")]
+pub async fn backfill_analysis_jobs(pool: &SqlitePool, persona_id: i64, since_days: i64) -> Result<u64> {
+	let _timer = DbTimer::new("backfill_analysis_jobs");
+	let result = sqlx::query("INSERT OR IGNORE INTO analysis_jobs (article_id, persona_id) SELECT id, ? FROM articles WHERE dead = 0 AND deleted = 0 AND timestamp >= unixepoch() - ? * 86400")
+		.bind(persona_id)
+		.bind(since_days)
+		.execute(pool)
+		.await?;
+
+	Ok(result.rows_affected())
+}
+
+// The first account becomes the admin
+#[cfg(feature = "ssr")]
+pub async fn create_user(pool: &SqlitePool, username: &str, password_hash: &str) -> Result<i64> {
+	let _timer = DbTimer::new("create_user");
+	let result = sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES (?, ?, NOT EXISTS (SELECT 1 FROM users WHERE is_admin))").bind(username).bind(password_hash).execute(pool).await?;
+
+	Ok(result.last_insert_rowid())
+}
+
+// User id and password hash for a login attempt (usernames are case-insensitive)
+#[cfg(feature = "ssr")]
+pub async fn get_user_credentials(pool: &SqlitePool, username: &str) -> Result<Option<(i64, String)>> {
+	let _timer = DbTimer::new("get_user_credentials");
+	let credentials = sqlx::query_as("SELECT id, password_hash FROM users WHERE username = ? AND id != 0").bind(username).fetch_optional(pool).await?;
+
+	Ok(credentials)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn create_session(pool: &SqlitePool, token_hash: &str, user_id: i64, ttl_days: u64) -> Result<()> {
+	let _timer = DbTimer::new("create_session");
+	// Opportunistic cleanup, logins are rare enough for this to be cheap
+	sqlx::query("DELETE FROM sessions WHERE expires_at <= CURRENT_TIMESTAMP").execute(pool).await?;
+	sqlx::query("INSERT INTO sessions (token_hash, user_id, expires_at) VALUES (?, ?, datetime('now', ?))").bind(token_hash).bind(user_id).bind(format!("+{} days", ttl_days)).execute(pool).await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn delete_session(pool: &SqlitePool, token_hash: &str) -> Result<()> {
+	let _timer = DbTimer::new("delete_session");
+	sqlx::query("DELETE FROM sessions WHERE token_hash = ?").bind(token_hash).execute(pool).await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_session_user(pool: &SqlitePool, token_hash: &str) -> Result<Option<CurrentUser>> {
+	let _timer = DbTimer::new("get_session_user");
+	let user = sqlx::query_as::<_, CurrentUser>(
+		r#"
+		SELECT u.id, u.username, p.name AS persona, u.is_admin
+		FROM sessions s
+		JOIN users u ON u.id = s.user_id
+		LEFT JOIN personas p ON p.id = u.persona_id AND p.active = 1
+		WHERE s.token_hash = ? AND s.expires_at > CURRENT_TIMESTAMP
+		"#,
+	)
+	.bind(token_hash)
+	.fetch_optional(pool)
+	.await?;
+
+	Ok(user)
+}
+
+// Returns false when no persona has that name
+#[cfg(feature = "ssr")]
+pub async fn set_user_persona(pool: &SqlitePool, user_id: i64, persona: &str) -> Result<bool> {
+	let _timer = DbTimer::new("set_user_persona");
+	let result = sqlx::query("UPDATE users SET persona_id = (SELECT id FROM personas WHERE name = ?) WHERE id = ? AND EXISTS (SELECT 1 FROM personas WHERE name = ? AND active = 1)")
+		.bind(persona)
+		.bind(user_id)
+		.bind(persona)
+		.execute(pool)
+		.await?;
+
+	Ok(result.rows_affected() > 0)
+}
+
+// Stories that are high-priority for at least one persona, whose discussion was never summarized, or has at least doubled since
+#[cfg(feature = "ssr")]
+pub async fn get_discussions_to_summarize(pool: &SqlitePool, min_priority: i64, min_comments: i64, max_attempts: i64, limit: i64) -> Result<Vec<Article>> {
+	let _timer = DbTimer::new("get_discussions_to_summarize");
+	let articles = sqlx::query_as::<_, Article>(
+		r#"
+		SELECT a.id, a.hn_id, a.title, a.url, a.score, a.timestamp, a.fetched_at, a.summary, a.key_points, a.feed,
+			a.author, a.descendants, a.item_type, a.kids, a.dead, a.deleted, a.discussion_summary, MAX(aa.priority) AS priority
+		FROM articles a
+		JOIN article_analyses aa ON aa.article_id = a.id AND aa.is_interesting = 1 AND aa.persona_id IN (SELECT id FROM personas WHERE active = 1)
+		WHERE a.dead = 0 AND a.deleted = 0 AND a.descendants >= ?
+			AND (a.discussion_summary IS NULL OR a.descendants >= a.discussion_summary_comments * 2)
+			AND a.discussion_attempts < ? AND (a.discussion_retry_at IS NULL OR a.discussion_retry_at <= CURRENT_TIMESTAMP)
+		GROUP BY a.id
+		HAVING MAX(aa.priority) >= ?
+		ORDER BY priority DESC, a.descendants DESC
+		LIMIT ?
+		"#,
+	)
+	.bind(min_comments)
+	.bind(max_attempts)
+	.bind(min_priority)
+	.bind(limit)
+	.fetch_all(pool)
+	.await?;
+
+	Ok(articles)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn update_discussion_summary(pool: &SqlitePool, article_id: i64, summary: &str, comments: i64) -> Result<()> {
+	let _timer = DbTimer::new("update_discussion_summary");
+	sqlx::query("UPDATE articles SET discussion_summary = ?, discussion_summary_comments = ?, discussion_attempts = 0, discussion_retry_at = NULL WHERE id = ?")
+		.bind(summary)
+		.bind(comments)
+		.bind(article_id)
+		.execute(pool)
+		.await?;
+
+	Ok(())
+}
+
+// Counts an unsuccessful summary and holds the article back: delay, 2x delay, 4x delay...
+#[cfg(feature = "ssr")]
+pub async fn fail_discussion_summary(pool: &SqlitePool, article_id: i64, retry_delay_minutes: i64) -> Result<()> {
+	let _timer = DbTimer::new("fail_discussion_summary");
+	sqlx::query("UPDATE articles SET discussion_attempts = discussion_attempts + 1, discussion_retry_at = datetime('now', '+' || (? << MIN(discussion_attempts, 10)) || ' minutes') WHERE id = ?")
+		.bind(retry_delay_minutes)
+		.bind(article_id)
+		.execute(pool)
+		.await?;
+
 	Ok(())
 }
 
+// Listing filters, empty category and None feed match everything
 #[cfg(feature = "ssr")]
-pub async fn get_interesting_articles(pool: &SqlitePool, sort_field: SortField, sort_direction: SortDirection, category: String) -> Result<Vec<Article>> {
+pub struct ArticleFilter {
+	pub category: String,
+	pub feed: Option<HnFeed>,
+	pub hide_read: bool,
+	// List what the analyzer filtered out instead of what it picked
+	pub rejected: bool,
+	// Hide job posts, for those stored before EXCLUDE_JOB_POSTS was set
+	pub exclude_jobs: bool,
+}
+
+// Joins and filters of the listing, shared with the unread count and "mark all read" so they cover the same articles.
+// Binds $1 category, $2 feed, $3 persona, $4 reader, $5 unread only, $6 interesting, $7 hide job posts
+#[cfg(feature = "ssr")]
+const LISTING_FROM: &str = r#"
+		FROM articles a
+		JOIN article_analyses aa ON aa.article_id = a.id
+		JOIN personas p ON p.id = aa.persona_id
+		LEFT JOIN article_reads r ON r.article_id = a.id AND r.user_id = $4
+		WHERE p.name = $3 AND aa.is_interesting = $6 AND a.dead = 0 AND a.deleted = 0 AND ($1 = '' OR aa.category = $1) AND ($2 = '' OR a.feed = $2) AND ($5 = 0 OR r.article_id IS NULL) AND ($7 = 0 OR a.item_type != 'job')
+"#;
+
+#[cfg(feature = "ssr")]
+pub async fn get_articles(pool: &SqlitePool, persona: &str, reader_id: i64, filter: ArticleFilter, sort_field: SortField, sort_direction: SortDirection) -> Result<Vec<Article>> {
+	let _timer = DbTimer::new("get_articles");
 	// Build ORDER BY clause dynamically
 	let order_by = match sort_field {
 		SortField::Date => match sort_direction {
@@ -84,20 +505,313 @@ pub async fn get_interesting_articles(pool: &SqlitePool, sort_field: SortField,
 			SortDirection::Descending => "priority DESC NULLS LAST, fetched_at DESC",
 			SortDirection::Ascending => "priority ASC NULLS LAST, fetched_at ASC",
 		},
+		SortField::Velocity => match sort_direction {
+			SortDirection::Descending => "velocity DESC, a.id DESC",
+			SortDirection::Ascending => "velocity ASC, a.id ASC",
+		},
 	};
 
 	let query = format!(
 		r#"
-		SELECT id, hn_id, title, url, score, timestamp, fetched_at, ai_analysis_done, is_interesting, reason, priority, category
-		FROM articles
-		WHERE is_interesting = 1 AND ($1 = '' OR category = $1)
+		SELECT a.id, a.hn_id, a.title, a.url, a.score, a.timestamp, a.fetched_at, 1 AS ai_analysis_done, aa.is_interesting, aa.reason, aa.priority, aa.category,
+			a.summary, a.key_points, a.feed, a.author, a.descendants, a.item_type, a.kids, a.dead, a.deleted, a.discussion_summary, aa.overridden,
+			-- Stories younger than an hour count as one hour old, so a few early votes don't top the list
+			a.score * 3600.0 / MAX(unixepoch() - a.timestamp, 3600) AS velocity,
+			r.article_id IS NOT NULL AS is_read,
+			EXISTS (SELECT 1 FROM bookmarks b WHERE b.article_id = a.id AND b.user_id = $4) AS is_bookmarked,
+			COALESCE((SELECT f.vote FROM article_feedback f WHERE f.article_id = a.id AND f.persona_id = p.id AND f.user_id = $4), 0) AS feedback
+		{}
 		ORDER BY {}
-		LIMIT 50
+		LIMIT $8
 		"#,
-		order_by
+		LISTING_FROM, order_by
 	);
 
-	let articles = sqlx::query_as::<_, Article>(&query).bind(category).fetch_all(pool).await?;
+	let articles = sqlx::query_as::<_, Article>(&query)
+		.bind(filter.category)
+		.bind(filter.feed.map(|f| f.as_str()).unwrap_or(""))
+		.bind(persona)
+		.bind(reader_id)
+		.bind(filter.hide_read)
+		.bind(!filter.rejected)
+		.bind(filter.exclude_jobs)
+		.bind(LISTING_LIMIT)
+		.fetch_all(pool)
+		.await?;
 
 	Ok(articles)
 }
+
+#[cfg(feature = "ssr")]
+pub async fn mark_articles_read(pool: &SqlitePool, user_id: i64, article_ids: &[i64]) -> Result<u64> {
+	let _timer = DbTimer::new("mark_articles_read");
+	let result = sqlx::query("INSERT OR IGNORE INTO article_reads (user_id, article_id) SELECT ?, id FROM articles WHERE id IN (SELECT value FROM json_each(?))")
+		.bind(user_id)
+		.bind(serde_json::to_string(article_ids)?)
+		.execute(pool)
+		.await?;
+
+	Ok(result.rows_affected())
+}
+
+// Marks every article matching the listing's filters read, including those past the first page
+#[cfg(feature = "ssr")]
+pub async fn mark_all_read(pool: &SqlitePool, persona: &str, reader_id: i64, filter: ArticleFilter) -> Result<u64> {
+	let _timer = DbTimer::new("mark_all_read");
+	let query = format!("INSERT OR IGNORE INTO article_reads (user_id, article_id) SELECT $4, a.id {}", LISTING_FROM);
+	let result = sqlx::query(&query)
+		.bind(filter.category)
+		.bind(filter.feed.map(|f| f.as_str()).unwrap_or(""))
+		.bind(persona)
+		.bind(reader_id)
+		.bind(true)
+		.bind(!filter.rejected)
+		.bind(filter.exclude_jobs)
+		.execute(pool)
+		.await?;
+
+	Ok(result.rows_affected())
+}
+
+// Unread articles matching the listing's filters, counted up to one past a page so the UI can show "50+"
+#[cfg(feature = "ssr")]
+pub async fn count_unread(pool: &SqlitePool, persona: &str, reader_id: i64, filter: ArticleFilter) -> Result<i64> {
+	let _timer = DbTimer::new("count_unread");
+	let query = format!("SELECT COUNT(*) FROM (SELECT 1 {} LIMIT $8)", LISTING_FROM);
+	let count = sqlx::query_scalar(&query)
+		.bind(filter.category)
+		.bind(filter.feed.map(|f| f.as_str()).unwrap_or(""))
+		.bind(persona)
+		.bind(reader_id)
+		.bind(true)
+		.bind(!filter.rejected)
+		.bind(filter.exclude_jobs)
+		.bind(LISTING_LIMIT + 1)
+		.fetch_one(pool)
+		.await?;
+
+	Ok(count)
+}
+
+// Saves the article, or updates the note and tags when it is already saved
+#[cfg(feature = "ssr")]
+pub async fn upsert_bookmark(pool: &SqlitePool, user_id: i64, article_id: i64, note: &str, tags: &[String]) -> Result<()> {
+	let _timer = DbTimer::new("upsert_bookmark");
+	sqlx::query(
+		r#"
+		INSERT INTO bookmarks (user_id, article_id, note, tags) VALUES (?, ?, ?, ?)
+		ON CONFLICT(user_id, article_id) DO UPDATE SET note = excluded.note, tags = excluded.tags, updated_at = CURRENT_TIMESTAMP
+		"#,
+	)
+	.bind(user_id)
+	.bind(article_id)
+	.bind(note)
+	.bind(serde_json::to_string(tags)?)
+	.execute(pool)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn delete_bookmark(pool: &SqlitePool, user_id: i64, article_id: i64) -> Result<()> {
+	let _timer = DbTimer::new("delete_bookmark");
+	sqlx::query("DELETE FROM bookmarks WHERE user_id = ? AND article_id = ?").bind(user_id).bind(article_id).execute(pool).await?;
+
+	Ok(())
+}
+
+// Newest saves first, with the persona's analysis when there is one; dead and deleted stories stay on the list
+#[cfg(feature = "ssr")]
+pub async fn get_bookmarks(pool: &SqlitePool, user_id: i64, persona: &str) -> Result<Vec<SavedArticle>> {
+	let _timer = DbTimer::new("get_bookmarks");
+	let bookmarks = sqlx::query_as::<_, SavedArticle>(
+		r#"
+		SELECT a.id, a.hn_id, a.title, a.url, a.score, a.timestamp, a.fetched_at, aa.article_id IS NOT NULL AS ai_analysis_done, COALESCE(aa.is_interesting, 0) AS is_interesting,
+			aa.reason, aa.priority, aa.category, a.summary, a.key_points, a.feed, a.author, a.descendants, a.item_type, a.kids, a.dead, a.deleted, a.discussion_summary,
+			COALESCE(aa.overridden, 0) AS overridden, a.score * 3600.0 / MAX(unixepoch() - a.timestamp, 3600) AS velocity,
+			r.article_id IS NOT NULL AS is_read, 1 AS is_bookmarked, COALESCE(f.vote, 0) AS feedback,
+			b.note, b.tags, b.created_at AS saved_at
+		FROM bookmarks b
+		JOIN articles a ON a.id = b.article_id
+		LEFT JOIN personas p ON p.name = ?
+		LEFT JOIN article_analyses aa ON aa.article_id = a.id AND aa.persona_id = p.id
+		LEFT JOIN article_reads r ON r.article_id = a.id AND r.user_id = b.user_id
+		LEFT JOIN article_feedback f ON f.article_id = a.id AND f.persona_id = p.id AND f.user_id = b.user_id
+		WHERE b.user_id = ?
+		ORDER BY b.created_at DESC, a.id DESC
+		"#,
+	)
+	.bind(persona)
+	.bind(user_id)
+	.fetch_all(pool)
+	.await?;
+
+	Ok(bookmarks)
+}
+
+// A vote of 0 clears the reader's feedback; returns false when no persona has that name
+#[cfg(feature = "ssr")]
+pub async fn set_feedback(pool: &SqlitePool, user_id: i64, article_id: i64, persona: &str, vote: i64) -> Result<bool> {
+	let _timer = DbTimer::new("set_feedback");
+	let result = if vote == 0 {
+		sqlx::query("DELETE FROM article_feedback WHERE user_id = ? AND article_id = ? AND persona_id = (SELECT id FROM personas WHERE name = ?)")
+			.bind(user_id)
+			.bind(article_id)
+			.bind(persona)
+			.execute(pool)
+			.await?
+	} else {
+		sqlx::query(
+			r#"
+			INSERT INTO article_feedback (user_id, article_id, persona_id, vote)
+			SELECT ?, ?, id, ? FROM personas WHERE name = ?
+			ON CONFLICT(user_id, article_id, persona_id) DO UPDATE SET vote = excluded.vote, updated_at = CURRENT_TIMESTAMP
+			"#,
+		)
+		.bind(user_id)
+		.bind(article_id)
+		.bind(vote.signum())
+		.bind(persona)
+		.execute(pool)
+		.await?
+	};
+
+	Ok(vote == 0 || result.rows_affected() > 0)
+}
+
+// Most recently voted articles for the persona, up to `limit` relevant and `limit` irrelevant ones;
+// readers who disagree on an article are outvoted, ties are left out
+#[cfg(feature = "ssr")]
+pub async fn get_feedback_examples(pool: &SqlitePool, persona_id: i64, limit: i64) -> Result<Vec<FeedbackExample>> {
+	let _timer = DbTimer::new("get_feedback_examples");
+	let examples = sqlx::query_as::<_, FeedbackExample>(
+		r#"
+		SELECT article_id, title, relevant FROM (
+			SELECT a.id AS article_id, a.title, SUM(f.vote) > 0 AS relevant,
+				ROW_NUMBER() OVER (PARTITION BY SUM(f.vote) > 0 ORDER BY MAX(f.updated_at) DESC, a.id DESC) AS n
+			FROM article_feedback f
+			JOIN articles a ON a.id = f.article_id
+			WHERE f.persona_id = ?
+			GROUP BY a.id
+			HAVING SUM(f.vote) != 0
+		)
+		WHERE n <= ?
+		ORDER BY relevant DESC, n
+		"#,
+	)
+	.bind(persona_id)
+	.bind(limit)
+	.fetch_all(pool)
+	.await?;
+
+	Ok(examples)
+}
+
+// Snapshots shown per sparkline
+#[cfg(feature = "ssr")]
+pub const SCORE_HISTORY_POINTS: i64 = 24;
+
+// Most recent scores per article, oldest first
+#[cfg(feature = "ssr")]
+pub async fn get_score_histories(pool: &SqlitePool, article_ids: &[i64], points: i64) -> Result<HashMap<i64, Vec<i64>>> {
+	let _timer = DbTimer::new("get_score_histories");
+	let rows: Vec<(i64, i64)> = sqlx::query_as(
+		r#"
+		SELECT article_id, score FROM (
+			SELECT article_id, score, captured_at, id, ROW_NUMBER() OVER (PARTITION BY article_id ORDER BY captured_at DESC, id DESC) AS n
+			FROM score_snapshots
+			WHERE article_id IN (SELECT value FROM json_each(?))
+		)
+		WHERE n <= ?
+		ORDER BY article_id, captured_at, id
+		"#,
+	)
+	.bind(serde_json::to_string(article_ids)?)
+	.bind(points)
+	.fetch_all(pool)
+	.await?;
+
+	let mut histories: HashMap<i64, Vec<i64>> = HashMap::new();
+	for (article_id, score) in rows {
+		histories.entry(article_id).or_default().push(score);
+	}
+
+	Ok(histories)
+}
+
+// Drop snapshots older than `keep_minutes`, keeping each article's latest one and the full history of bookmarked articles
+#[cfg(feature = "ssr")]
+pub async fn prune_score_snapshots(pool: &SqlitePool, keep_minutes: i64) -> Result<u64> {
+	let _timer = DbTimer::new("prune_score_snapshots");
+	let result = sqlx::query(
+		r#"
+		DELETE FROM score_snapshots
+		WHERE captured_at < datetime('now', ?)
+			AND article_id NOT IN (SELECT article_id FROM bookmarks)
+			AND id NOT IN (SELECT MAX(id) FROM score_snapshots GROUP BY article_id)
+		"#,
+	)
+	.bind(format!("-{} minutes", keep_minutes))
+	.execute(pool)
+	.await?;
+
+	Ok(result.rows_affected())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn start_run(pool: &SqlitePool, kind: &str) -> Result<i64> {
+	let _timer = DbTimer::new("start_run");
+	let result = sqlx::query("INSERT INTO aggregator_runs (kind) VALUES (?)").bind(kind).execute(pool).await?;
+
+	Ok(result.last_insert_rowid())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn finish_run(pool: &SqlitePool, run_id: i64, metrics: &RunMetrics) -> Result<()> {
+	let _timer = DbTimer::new("finish_run");
+	sqlx::query(
+		r#"
+		UPDATE aggregator_runs
+		SET finished_at = CURRENT_TIMESTAMP, duration_ms = ?, stories_fetched = ?, new_stories = ?, updated_stories = ?,
+			analyzed = ?, rejected = ?, llm_errors = ?, avg_llm_latency_ms = ?, error = ?, idle = ?
+		WHERE id = ?
+		"#,
+	)
+	.bind(metrics.duration_ms)
+	.bind(metrics.stories_fetched)
+	.bind(metrics.new_stories)
+	.bind(metrics.updated_stories)
+	.bind(metrics.analyzed)
+	.bind(metrics.rejected)
+	.bind(metrics.llm_errors)
+	.bind(metrics.avg_llm_latency_ms)
+	.bind(&metrics.error)
+	.bind(metrics.idle)
+	.bind(run_id)
+	.execute(pool)
+	.await?;
+
+	Ok(())
+}
+
+// Idle analysis cycles would drown the history, they are only listed on request
+#[cfg(feature = "ssr")]
+pub async fn get_recent_runs(pool: &SqlitePool, limit: i64, include_idle: bool) -> Result<Vec<AggregatorRun>> {
+	let _timer = DbTimer::new("get_recent_runs");
+	let runs = sqlx::query_as::<_, AggregatorRun>(
+		r#"
+		SELECT id, kind, started_at, finished_at, duration_ms, stories_fetched, new_stories, updated_stories, analyzed, rejected, llm_errors, avg_llm_latency_ms, error, idle
+		FROM aggregator_runs
+		WHERE ? OR idle = 0
+		ORDER BY id DESC
+		LIMIT ?
+		"#,
+	)
+	.bind(include_idle)
+	.bind(limit)
+	.fetch_all(pool)
+	.await?;
+
+	Ok(runs)
+}
@@ -0,0 +1,108 @@
+#[cfg(feature = "ssr")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use axum::{http::StatusCode, Json};
+
+#[cfg(feature = "ssr")]
+use serde::Serialize;
+
+#[cfg(feature = "ssr")]
+use tokio::time::Duration;
+
+#[cfg(feature = "ssr")]
+use crate::{db::models::WorkerRunStatus, state::AppState};
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+	pub status: &'static str,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+	pub ok: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<Result<()>> for CheckResult {
+	fn from(result: Result<()>) -> Self {
+		match result {
+			Ok(()) => Self { ok: true, error: None },
+			Err(e) => Self { ok: false, error: Some(format!("{:#}", e)) },
+		}
+	}
+}
+
+// Liveness: the process is up and serving requests
+#[cfg(feature = "ssr")]
+pub async fn healthz() -> Json<HealthReport> {
+	Json(HealthReport { status: "ok", checks: BTreeMap::new() })
+}
+
+// Readiness: everything the aggregator depends on is usable, 503 otherwise
+#[cfg(feature = "ssr")]
+pub async fn readyz(state: AppState) -> (StatusCode, Json<HealthReport>) {
+	let mut checks = BTreeMap::new();
+	checks.insert("database", check_database(&state.db_pool).await.into());
+	checks.insert("migrations", check_migrations(&state.db_pool).await.into());
+	checks.insert("analyzer", check_analyzer(&state).await.into());
+
+	let status = state.aggregator.status();
+	checks.insert("fetch_cycle", check_fresh(&status.fetch, state.config.fetch_interval_minutes).into());
+	checks.insert("analysis_cycle", check_fresh(&status.analysis, state.config.analysis_interval_minutes).into());
+
+	if checks.values().all(|c: &CheckResult| c.ok) {
+		(StatusCode::OK, Json(HealthReport { status: "ok", checks }))
+	} else {
+		(StatusCode::SERVICE_UNAVAILABLE, Json(HealthReport { status: "unavailable", checks }))
+	}
+}
+
+#[cfg(feature = "ssr")]
+async fn check_database(pool: &sqlx::SqlitePool) -> Result<()> {
+	sqlx::query("SELECT 1").execute(pool).await?;
+	Ok(())
+}
+
+// Every migration embedded in this binary has been applied successfully
+#[cfg(feature = "ssr")]
+async fn check_migrations(pool: &sqlx::SqlitePool) -> Result<()> {
+	let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = 1").fetch_all(pool).await?;
+
+	let missing: Vec<String> = sqlx::migrate!("./migrations").iter().filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version)).map(|m| m.version.to_string()).collect();
+	anyhow::ensure!(missing.is_empty(), "Pending migrations: {}", missing.join(", "));
+
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+async fn check_analyzer(state: &AppState) -> Result<()> {
+	match tokio::time::timeout(Duration::from_secs(10), state.analyzer.health_check()).await {
+		Ok(result) => result,
+		Err(_) => anyhow::bail!("'{}' analyzer health check timed out", state.analyzer.name()),
+	}
+}
+
+// A worker is stale when it has missed two scheduled cycles; a long cycle still in progress is not stale
+#[cfg(feature = "ssr")]
+fn check_fresh(worker: &WorkerRunStatus, interval_minutes: u64) -> Result<()> {
+	if worker.running {
+		return Ok(());
+	}
+
+	let max_age_secs = interval_minutes * 60 * 2;
+	match worker.last_run_secs_ago {
+		Some(secs) if secs <= max_age_secs => Ok(()),
+		Some(secs) => anyhow::bail!("Last cycle started {}s ago, expected at most {}s", secs, max_age_secs),
+		None => anyhow::bail!("No cycle has run yet"),
+	}
+}
@@ -2,9 +2,18 @@ pub mod app;
 pub mod components;
 pub mod db;
 
+#[cfg(feature = "ssr")]
+pub mod auth;
+
 #[cfg(feature = "ssr")]
 pub mod config;
 
+#[cfg(feature = "ssr")]
+pub mod health;
+
+#[cfg(feature = "ssr")]
+pub mod metrics;
+
 #[cfg(feature = "ssr")]
 pub mod state;
 
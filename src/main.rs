@@ -1,21 +1,31 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-	use axum::Router;
+	use axum::{middleware, routing::get, Router};
 	use hn_smart_aggregator::app::*;
 	use hn_smart_aggregator::config::Config;
-	use hn_smart_aggregator::services::aggregator;
+	use hn_smart_aggregator::db::repository;
+	use hn_smart_aggregator::{auth, health, metrics};
+	use hn_smart_aggregator::services::{
+		aggregator::{self, AggregatorControl},
+		analyzer,
+		circuit_breaker::CircuitBreaker,
+	};
 	use hn_smart_aggregator::state::AppState;
 	use leptos::config::get_configuration;
 	use leptos::prelude::*;
 	use leptos_axum::{generate_route_list, LeptosRoutes};
 	use std::sync::Arc;
+	use tokio_util::sync::CancellationToken;
 
 	// Initialize logging
 	tracing_subscriber::fmt().with_env_filter(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))).init();
 
 	tracing::info!("Starting Smart HN Aggregator...");
 
+	// Install the metrics recorder before anything records into it
+	let metrics_handle = metrics::install()?;
+
 	// Load configuration
 	let config = Arc::new(Config::load().await?);
 	tracing::info!("Configuration loaded");
@@ -28,16 +38,68 @@ async fn main() -> anyhow::Result<()> {
 	sqlx::migrate!("./migrations").run(&db_pool).await?;
 	tracing::info!("Migrations completed");
 
+	// Personas live in the database, the files only seed and update them
+	for persona in &config.personas {
+		let persona_id = repository::upsert_persona(&db_pool, &persona.name, &persona.description).await?;
+		// New (or returning) personas get the recent stories fetched before them, a no-op for the others
+		let queued = repository::backfill_analysis_jobs(&db_pool, persona_id, config.persona_backfill_days).await?;
+		if queued > 0 {
+			tracing::info!("Queued {} recent articles for analysis with persona '{}'", queued, persona.name);
+		}
+	}
+	let names: Vec<String> = config.personas.iter().map(|p| p.name.clone()).collect();
+	for name in repository::deactivate_personas(&db_pool, &names).await? {
+		tracing::warn!("Persona '{}' is no longer configured, deactivated it (its analyses are kept)", name);
+	}
+	tracing::info!("{} personas synced", config.personas.len());
+
+	// Shared between the analysis worker and the readiness probe
+	let analyzer = analyzer::from_config(&config)?;
+	tracing::info!("Using '{}' analyzer", analyzer.name());
+
+	// Analyses aren't redone on their own when the persona, categories or model change, point at the endpoint that does it
+	for persona in repository::get_personas(&db_pool).await? {
+		let stamp = analyzer::analysis_stamp(&persona.description, &config.categories, &analyzer.model());
+		let stale = repository::count_stale_analyses(&db_pool, persona.id, &stamp, None).await?;
+		if stale > 0 {
+			tracing::warn!("{} analyses for '{}' were made with another persona, category list or model; POST /api/requeue_stale to redo them", stale, persona.name);
+		}
+	}
+
+	// Shared between the analysis worker (which trips it) and the UI (which reports it)
+	let analyzer_breaker = Arc::new(CircuitBreaker::new(config.breaker_failure_threshold, std::time::Duration::from_secs(config.breaker_cooldown_secs)));
+
+	// Run tracking and manual refresh triggers, shared between the workers and the UI
+	let aggregator_control = Arc::new(AggregatorControl::default());
+
 	// Create app state
-	let app_state = AppState { db_pool: db_pool.clone(), config: config.clone() };
+	let app_state = AppState { db_pool: db_pool.clone(), config: config.clone(), analyzer: analyzer.clone(), analyzer_breaker: analyzer_breaker.clone(), aggregator: aggregator_control.clone(), login_throttle: Arc::default() };
+
+	// Cancelled on SIGINT/SIGTERM, every long-running task watches it
+	let shutdown = CancellationToken::new();
+	tokio::spawn(shutdown_signal(shutdown.clone()));
+
+	// Histograms are only drained on upkeep, keep memory bounded even when nobody scrapes
+	let upkeep_handle = metrics_handle.clone();
+	let upkeep_shutdown = shutdown.clone();
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+		loop {
+			tokio::select! {
+				_ = upkeep_shutdown.cancelled() => break,
+				_ = interval.tick() => upkeep_handle.run_upkeep(),
+			}
+		}
+	});
 
-	// Spawn background worker
+	// Spawn background workers
 	let worker_pool = db_pool.clone();
 	let worker_config = config.clone();
-	tokio::spawn(async move {
-		tracing::info!("Starting background aggregator worker...");
-		if let Err(e) = aggregator::run_aggregator_loop(worker_pool, worker_config).await {
-			tracing::error!("Background worker failed: {}", e);
+	let worker_shutdown = shutdown.clone();
+	let workers = tokio::spawn(async move {
+		tracing::info!("Starting background fetch and analysis workers...");
+		if let Err(e) = aggregator::run_workers(worker_pool, worker_config, analyzer, analyzer_breaker, aggregator_control, worker_shutdown).await {
+			tracing::error!("Background workers failed: {}", e);
 		}
 	});
 
@@ -61,17 +123,81 @@ async fn main() -> anyhow::Result<()> {
 				move || shell(leptos_options.clone())
 			},
 		)
+		.route("/healthz", get(health::healthz))
+		.route("/readyz", {
+			let app_state = app_state.clone();
+			get(move || health::readyz(app_state.clone()))
+		})
+		.route("/metrics", {
+			let token = config.metrics_token.clone();
+			get(move |headers: axum::http::HeaderMap| std::future::ready(metrics::scrape(&metrics_handle, token.as_deref(), &headers)))
+		})
+		// Inside the metrics layer so rejected requests are still counted
+		.layer(middleware::from_fn_with_state(app_state.clone(), auth::session_middleware))
+		.layer(middleware::from_fn(metrics::track_server_fns))
 		.fallback(leptos_axum::file_and_error_handler(shell))
 		.with_state(leptos_options);
 
 	// Start server
 	tracing::info!("Server listening on http://{}", &addr);
 	let listener = tokio::net::TcpListener::bind(&addr).await?;
-	axum::serve(listener, app.into_make_service()).await?;
+	// Stops accepting connections on shutdown and lets in-flight requests complete
+	// Peer addresses feed the login throttle
+	axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).with_graceful_shutdown(shutdown.clone().cancelled_owned()).await?;
+	tracing::info!("Server stopped accepting requests");
+
+	// Let in-flight analyses finish, but don't hang forever on a stuck upstream
+	tracing::info!("Waiting up to {}s for background workers to stop...", config.shutdown_timeout_secs);
+	let workers_abort = workers.abort_handle();
+	match tokio::time::timeout(std::time::Duration::from_secs(config.shutdown_timeout_secs), workers).await {
+		Ok(Ok(())) => tracing::info!("Background workers stopped"),
+		Ok(Err(e)) => tracing::error!("Background workers panicked: {}", e),
+		Err(_) => {
+			tracing::warn!("Background workers did not stop in time, aborting them");
+			workers_abort.abort();
+		}
+	}
+
+	// Close the pool last so every pending write is flushed before the process exits
+	db_pool.close().await;
+	tracing::info!("Database connections closed, shutdown complete");
 
 	Ok(())
 }
 
+#[cfg(feature = "ssr")]
+async fn shutdown_signal(shutdown: tokio_util::sync::CancellationToken) {
+	let ctrl_c = async {
+		if let Err(e) = tokio::signal::ctrl_c().await {
+			tracing::error!("Failed to listen for Ctrl+C: {}", e);
+			std::future::pending::<()>().await;
+		}
+	};
+
+	#[cfg(unix)]
+	let terminate = async {
+		match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+			Ok(mut signal) => {
+				signal.recv().await;
+			}
+			Err(e) => {
+				tracing::error!("Failed to listen for SIGTERM: {}", e);
+				std::future::pending::<()>().await;
+			}
+		}
+	};
+
+	#[cfg(not(unix))]
+	let terminate = std::future::pending::<()>();
+
+	tokio::select! {
+		_ = ctrl_c => tracing::info!("Received SIGINT, shutting down..."),
+		_ = terminate => tracing::info!("Received SIGTERM, shutting down..."),
+	}
+
+	shutdown.cancel();
+}
+
 #[cfg(not(feature = "ssr"))]
 pub fn main() {
 	// Client-side main
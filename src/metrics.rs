@@ -0,0 +1,138 @@
+#[cfg(feature = "ssr")]
+use std::{
+	collections::HashSet,
+	sync::OnceLock,
+	time::{Duration, Instant},
+};
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use axum::{
+	extract::Request,
+	http::{header, HeaderMap, StatusCode},
+	middleware::Next,
+	response::{IntoResponse, Response},
+};
+
+#[cfg(feature = "ssr")]
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+// Latency buckets in seconds, from fast DB queries to slow local LLM calls
+#[cfg(feature = "ssr")]
+const LATENCY_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+// Paths of the registered server fns, anything else under /api/ shares the "unknown" label
+#[cfg(feature = "ssr")]
+static SERVER_FN_PATHS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+
+// Install the global recorder; the handle renders the /metrics page
+#[cfg(feature = "ssr")]
+pub fn install() -> Result<PrometheusHandle> {
+	let handle = PrometheusBuilder::new().set_buckets(LATENCY_BUCKETS)?.install_recorder()?;
+
+	describe();
+	server_fn_paths();
+	Ok(handle)
+}
+
+// The /metrics page; with METRICS_TOKEN set, scrapers present it as a bearer token
+#[cfg(feature = "ssr")]
+pub fn scrape(handle: &PrometheusHandle, token: Option<&str>, headers: &HeaderMap) -> Response {
+	if let Some(token) = token {
+		let presented = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok()).and_then(|value| value.strip_prefix("Bearer ")).unwrap_or("");
+		// Compare digests so the time taken doesn't depend on how much of the token matched
+		if crate::auth::token_digest(presented) != crate::auth::token_digest(token) {
+			return StatusCode::UNAUTHORIZED.into_response();
+		}
+	}
+
+	handle.render().into_response()
+}
+
+#[cfg(feature = "ssr")]
+fn server_fn_paths() -> &'static HashSet<&'static str> {
+	SERVER_FN_PATHS.get_or_init(|| leptos::server_fn::axum::server_fn_paths().map(|(path, _)| path).collect())
+}
+
+#[cfg(feature = "ssr")]
+fn describe() {
+	metrics::describe_counter!("hn_requests_total", "HN API requests by kind (feed, item) and outcome");
+	metrics::describe_histogram!("hn_request_duration_seconds", metrics::Unit::Seconds, "HN API request latency, retries included");
+	metrics::describe_counter!("analyzer_requests_total", "Analyzer calls by provider and outcome");
+	metrics::describe_histogram!("analyzer_request_duration_seconds", metrics::Unit::Seconds, "Analyzer call latency, retries included");
+	metrics::describe_counter!("articles_categorized_total", "Analyzed articles by assigned category");
+	metrics::describe_counter!("analyzer_invalid_category_total", "Analyzer answers with an unknown category, coerced to Other");
+	metrics::describe_histogram!("db_query_duration_seconds", metrics::Unit::Seconds, "Repository query latency by query name");
+	metrics::describe_histogram!("server_fn_duration_seconds", metrics::Unit::Seconds, "Server function latency by endpoint and HTTP status");
+}
+
+#[cfg(feature = "ssr")]
+fn outcome(ok: bool) -> &'static str {
+	if ok {
+		"ok"
+	} else {
+		"error"
+	}
+}
+
+#[cfg(feature = "ssr")]
+pub fn record_hn_request(kind: &'static str, ok: bool, elapsed: Duration) {
+	metrics::counter!("hn_requests_total", "kind" => kind, "outcome" => outcome(ok)).increment(1);
+	metrics::histogram!("hn_request_duration_seconds", "kind" => kind).record(elapsed.as_secs_f64());
+}
+
+#[cfg(feature = "ssr")]
+pub fn record_analyzer_request(provider: &'static str, ok: bool, elapsed: Duration) {
+	metrics::counter!("analyzer_requests_total", "provider" => provider, "outcome" => outcome(ok)).increment(1);
+	metrics::histogram!("analyzer_request_duration_seconds", "provider" => provider).record(elapsed.as_secs_f64());
+}
+
+#[cfg(feature = "ssr")]
+pub fn record_category(category: &str) {
+	metrics::counter!("articles_categorized_total", "category" => category.to_string()).increment(1);
+}
+
+#[cfg(feature = "ssr")]
+pub fn record_invalid_category() {
+	metrics::counter!("analyzer_invalid_category_total").increment(1);
+}
+
+// Axum middleware timing server function calls; other routes pass through untouched
+#[cfg(feature = "ssr")]
+pub async fn track_server_fns(request: Request, next: Next) -> Response {
+	let path = request.uri().path();
+	let Some(name) = path.strip_prefix("/api/") else {
+		return next.run(request).await;
+	};
+
+	// Labelled by the full registered name: generated ones end with a hash of the crate dir and module, stable for a build
+	// setup, and trimming it could make an explicit endpoint ending in digits collide with another one
+	let endpoint = if server_fn_paths().contains(path) { name.to_string() } else { "unknown".to_string() };
+	let started = Instant::now();
+	let response = next.run(request).await;
+	metrics::histogram!("server_fn_duration_seconds", "endpoint" => endpoint, "status" => response.status().as_u16().to_string()).record(started.elapsed().as_secs_f64());
+	response
+}
+
+// Records the query latency when dropped, so early returns through `?` are timed too
+#[cfg(feature = "ssr")]
+pub struct DbTimer {
+	query: &'static str,
+	started: Instant,
+}
+
+#[cfg(feature = "ssr")]
+impl DbTimer {
+	pub fn new(query: &'static str) -> Self {
+		Self { query, started: Instant::now() }
+	}
+}
+
+#[cfg(feature = "ssr")]
+impl Drop for DbTimer {
+	fn drop(&mut self) {
+		metrics::histogram!("db_query_duration_seconds", "query" => self.query).record(self.started.elapsed().as_secs_f64());
+	}
+}
@@ -1,40 +1,176 @@
-use crate::db::models::{Article, CategoryCount, SortDirection, SortField};
+use crate::db::models::{Article, CategoryCount, HnFeed, SortDirection, SortField};
 use leptos::prelude::*;
 
 #[cfg(feature = "ssr")]
 use crate::state::AppState;
 
 #[server]
-pub async fn get_interesting_articles(sort_field: SortField, sort_direction: SortDirection, category: String) -> Result<Vec<Article>, ServerFnError> {
+pub async fn get_interesting_articles(persona: String, hide_read: bool, sort_field: SortField, sort_direction: SortDirection, category: String, feed: Option<HnFeed>) -> Result<Vec<Article>, ServerFnError> {
+	list_articles(persona, category, feed, hide_read, false, sort_field, sort_direction).await
+}
+
+// What the analyzer filtered out, with its reasons, to audit false negatives
+#[server]
+pub async fn get_rejected_articles(persona: String, hide_read: bool, sort_field: SortField, sort_direction: SortDirection, category: String, feed: Option<HnFeed>) -> Result<Vec<Article>, ServerFnError> {
+	list_articles(persona, category, feed, hide_read, true, sort_field, sort_direction).await
+}
+
+#[cfg(feature = "ssr")]
+async fn list_articles(persona: String, category: String, feed: Option<HnFeed>, hide_read: bool, rejected: bool, sort_field: SortField, sort_direction: SortDirection) -> Result<Vec<Article>, ServerFnError> {
+	use crate::db::repository::{self, ArticleFilter};
+	let state = expect_context::<AppState>();
+
+	let filter = ArticleFilter { category, feed, hide_read, rejected, exclude_jobs: state.config.exclude_job_posts };
+
+	let persona = super::personas::resolve_persona(persona);
+	let mut articles = repository::get_articles(&state.db_pool, &persona, crate::auth::current_user_id(), filter, sort_field, sort_direction).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	// Attach the last day of hourly-ish snapshots for the sparklines
+	let ids: Vec<i64> = articles.iter().map(|a| a.id).collect();
+	let mut histories = repository::get_score_histories(&state.db_pool, &ids, repository::SCORE_HISTORY_POINTS).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	for article in &mut articles {
+		article.score_history = histories.remove(&article.id).unwrap_or_default();
+	}
+
+	Ok(articles)
+}
+
+// Read marks belong to the signed-in user, or are shared by visitors who are not signed in
+#[server]
+pub async fn mark_articles_read(article_ids: Vec<i64>) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	repository::mark_articles_read(&state.db_pool, crate::auth::current_user_id(), &article_ids).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	Ok(())
+}
+
+// Everything the listing's filters match, not only the page shown
+#[server]
+pub async fn mark_all_read(persona: String, rejected: bool, category: String, feed: Option<HnFeed>) -> Result<(), ServerFnError> {
+	use crate::db::repository::{self, ArticleFilter};
+	let state = expect_context::<AppState>();
+
+	let persona = super::personas::resolve_persona(persona);
+	let filter = ArticleFilter { category, feed, hide_read: true, rejected, exclude_jobs: state.config.exclude_job_posts };
+	repository::mark_all_read(&state.db_pool, &persona, crate::auth::current_user_id(), filter).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	Ok(())
+}
+
+// Unread articles under the listing's filters, above LISTING_LIMIT only tells there are more than a page
+#[server]
+pub async fn get_unread_count(persona: String, rejected: bool, category: String, feed: Option<HnFeed>) -> Result<i64, ServerFnError> {
+	use crate::db::repository::{self, ArticleFilter};
+	let state = expect_context::<AppState>();
+
+	let persona = super::personas::resolve_persona(persona);
+	let filter = ArticleFilter { category, feed, hide_read: true, rejected, exclude_jobs: state.config.exclude_job_posts };
+	repository::count_unread(&state.db_pool, &persona, crate::auth::current_user_id(), filter).await.map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+// Thumbs up (1) or down (-1) on the article's relevance for the persona, 0 clears the vote
+#[server]
+pub async fn set_feedback(article_id: i64, persona: String, vote: i64) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	if !(-1..=1).contains(&vote) {
+		return Err(ServerFnError::new("Feedback must be 1, -1 or 0"));
+	}
+
+	let persona = super::personas::resolve_persona(persona);
+	let saved = repository::set_feedback(&state.db_pool, crate::auth::current_user_id(), article_id, &persona, vote).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	if !saved {
+		return Err(ServerFnError::new(format!("Unknown persona '{}'", persona)));
+	}
+
+	Ok(())
+}
+
+// Hand-set relevance, priority and category (admins only, analyses are shared); later analyses of the article keep them
+#[server]
+pub async fn override_analysis(article_id: i64, persona: String, is_interesting: bool, priority: i64, category: String) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	if !crate::auth::is_admin() {
+		return Err(ServerFnError::new("Only admins can edit analyses"));
+	}
+
+	if !(1..=5).contains(&priority) {
+		return Err(ServerFnError::new("Priority must be between 1 and 5"));
+	}
+	let Some(category) = state.config.categories.iter().find(|c| c.eq_ignore_ascii_case(&category)) else {
+		return Err(ServerFnError::new(format!("Unknown category '{}'", category)));
+	};
+
+	let persona = super::personas::resolve_persona(persona);
+	let saved = repository::override_analysis(&state.db_pool, article_id, &persona, crate::auth::current_user_id(), is_interesting, priority, category).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	if !saved {
+		return Err(ServerFnError::new(format!("Unknown persona '{}'", persona)));
+	}
+	tracing::info!("Analysis of article {} overridden for '{}'", article_id, persona);
+
+	Ok(())
+}
+
+// Hands the article back to the analyzer (admins only)
+#[server]
+pub async fn reset_analysis(article_id: i64, persona: String) -> Result<(), ServerFnError> {
 	use crate::db::repository;
 	let state = expect_context::<AppState>();
 
-	repository::get_interesting_articles(&state.db_pool, sort_field, sort_direction, category).await.map_err(|e| ServerFnError::ServerError(e.to_string()))
+	if !crate::auth::is_admin() {
+		return Err(ServerFnError::new("Only admins can edit analyses"));
+	}
+
+	let persona = super::personas::resolve_persona(persona);
+	repository::clear_override(&state.db_pool, article_id, &persona).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	// Analyze it now rather than at the next scheduled cycle
+	state.aggregator.analysis_wakeup.notify_one();
+
+	Ok(())
+}
+
+#[server]
+pub async fn get_categories() -> Result<Vec<String>, ServerFnError> {
+	let state = expect_context::<AppState>();
+	Ok(state.config.categories.clone())
 }
 
+// Counts the articles the listing would show, picked or (with `rejected`) filtered out
 #[server]
-pub async fn get_categories_with_counts() -> Result<Vec<CategoryCount>, ServerFnError> {
+pub async fn get_categories_with_counts(persona: String, rejected: bool) -> Result<Vec<CategoryCount>, ServerFnError> {
 	use crate::config::Config;
 	use crate::state::AppState;
 	use std::collections::HashMap;
 
 	let state = expect_context::<AppState>();
 	let config = Config::load().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	let persona = super::personas::resolve_persona(persona);
 
 	let db_counts = sqlx::query_as!(
 		CategoryCount,
 		r#"
-		SELECT category as "category!", COUNT(*) as "count!: i32"
-		FROM articles
-		WHERE is_interesting = 1 AND category IS NOT NULL AND category != ''
-		GROUP BY category
-		"#
+		SELECT aa.category as "category!", COUNT(*) as "count!: i32"
+		FROM article_analyses aa
+		JOIN personas p ON p.id = aa.persona_id
+		JOIN articles a ON a.id = aa.article_id
+		WHERE p.name = $1 AND aa.is_interesting != $3 AND aa.category IS NOT NULL AND aa.category != '' AND a.dead = 0 AND a.deleted = 0 AND ($2 = 0 OR a.item_type != 'job')
+		GROUP BY aa.category
+		"#,
+		persona,
+		state.config.exclude_job_posts,
+		rejected
 	)
 	.fetch_all(&state.db_pool)
 	.await
 	.map_err(|e: sqlx::Error| ServerFnError::new(e.to_string()))?;
 
-	let counts_map: HashMap<String, i32> = db_counts.into_iter().map(|row| (row.category, row.count as i32)).collect();
+	let counts_map: HashMap<String, i32> = db_counts.into_iter().map(|row| (row.category, row.count)).collect();
 
 	let mut final_categories: Vec<CategoryCount> = config
 		.categories
@@ -42,7 +178,7 @@ pub async fn get_categories_with_counts() -> Result<Vec<CategoryCount>, ServerFn
 		.map(|cat_name| CategoryCount { count: *counts_map.get(&cat_name).unwrap_or(&0), category: cat_name })
 		.collect();
 
-	final_categories.sort_by(|a, b| b.count.cmp(&a.count));
+	final_categories.sort_by_key(|c| std::cmp::Reverse(c.count));
 
 	Ok(final_categories)
 }
@@ -0,0 +1,110 @@
+use crate::db::models::CurrentUser;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::state::AppState;
+
+// Stable URL (POST /api/current_user), None when signed out
+#[server(endpoint = "current_user")]
+pub async fn get_current_user() -> Result<Option<CurrentUser>, ServerFnError> {
+	Ok(crate::auth::current_user())
+}
+
+#[server(endpoint = "register")]
+pub async fn register(username: String, password: String) -> Result<(), ServerFnError> {
+	use crate::{auth, db::repository};
+	let state = expect_context::<AppState>();
+
+	if !state.config.registration_enabled {
+		return Err(ServerFnError::new("Registration is disabled on this instance"));
+	}
+
+	let username = username.trim().to_string();
+	if !(3..=32).contains(&username.chars().count()) || !username.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+		return Err(ServerFnError::new("Usernames are 3 to 32 letters, digits, '_', '-' or '.'"));
+	}
+	if !(8..=auth::MAX_PASSWORD_CHARS).contains(&password.chars().count()) {
+		return Err(ServerFnError::new(format!("Passwords are 8 to {} characters", auth::MAX_PASSWORD_CHARS)));
+	}
+
+	let existing = repository::get_user_credentials(&state.db_pool, &username).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	if existing.is_some() {
+		return Err(ServerFnError::new("This username is already taken"));
+	}
+
+	let password_hash = auth::hash_password(password).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	let user_id = repository::create_user(&state.db_pool, &username, &password_hash).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	tracing::info!("User '{}' registered", username);
+
+	start_session(&state, user_id).await?;
+	leptos_axum::redirect("/");
+	Ok(())
+}
+
+#[server(endpoint = "login")]
+pub async fn login(username: String, password: String) -> Result<(), ServerFnError> {
+	use crate::{auth, db::repository};
+	use axum::{extract::ConnectInfo, http::request::Parts};
+	use std::net::SocketAddr;
+	let state = expect_context::<AppState>();
+
+	let username = username.trim();
+	let address = use_context::<Parts>().and_then(|parts| parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip()));
+	if !state.login_throttle.allow(username, address) {
+		return Err(ServerFnError::new("Too many failed logins, try again later"));
+	}
+
+	let credentials = if password.chars().count() > auth::MAX_PASSWORD_CHARS {
+		None
+	} else {
+		repository::get_user_credentials(&state.db_pool, username).await.map_err(|e| ServerFnError::new(e.to_string()))?
+	};
+
+	// Unknown users still cost a hash check, and get the same message as wrong passwords
+	let user_id = match credentials {
+		Some((user_id, password_hash)) => auth::verify_password(password, password_hash).await.then_some(user_id),
+		None => {
+			auth::verify_password(password, auth::DUMMY_PASSWORD_HASH.to_string()).await;
+			None
+		}
+	};
+	let Some(user_id) = user_id else {
+		state.login_throttle.record_failure(username, address);
+		return Err(ServerFnError::new("Invalid username or password"));
+	};
+	state.login_throttle.record_success(username);
+
+	start_session(&state, user_id).await?;
+	leptos_axum::redirect("/");
+	Ok(())
+}
+
+#[server(endpoint = "logout")]
+pub async fn logout() -> Result<(), ServerFnError> {
+	use crate::{auth, db::repository};
+	use axum::http::{header, request::Parts, HeaderValue};
+	let state = expect_context::<AppState>();
+
+	if let Some(token) = use_context::<Parts>().and_then(|parts| auth::session_token(&parts.headers)) {
+		repository::delete_session(&state.db_pool, &auth::token_digest(&token)).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	}
+
+	let cookie = auth::session_cookie("", 0, state.config.session_cookie_secure);
+	expect_context::<leptos_axum::ResponseOptions>().insert_header(header::SET_COOKIE, HeaderValue::from_str(&cookie)?);
+	leptos_axum::redirect("/");
+	Ok(())
+}
+
+#[cfg(feature = "ssr")]
+async fn start_session(state: &AppState, user_id: i64) -> Result<(), ServerFnError> {
+	use crate::{auth, db::repository};
+	use axum::http::{header, HeaderValue};
+
+	let token = auth::new_session_token();
+	repository::create_session(&state.db_pool, &auth::token_digest(&token), user_id, state.config.session_ttl_days).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	let cookie = auth::session_cookie(&token, state.config.session_ttl_days * 24 * 3600, state.config.session_cookie_secure);
+	expect_context::<leptos_axum::ResponseOptions>().insert_header(header::SET_COOKIE, HeaderValue::from_str(&cookie)?);
+
+	Ok(())
+}
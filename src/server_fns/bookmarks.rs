@@ -0,0 +1,69 @@
+use crate::db::models::SavedArticle;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::state::AppState;
+
+// Limits on what a reader can store per bookmark
+pub const MAX_NOTE_CHARS: usize = 2000;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_CHARS: usize = 32;
+
+// Saves the article to the reader's list, or updates its note and comma-separated tags
+#[server]
+pub async fn add_bookmark(article_id: i64, note: String, tags: String) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	let note = note.trim();
+	if note.chars().count() > MAX_NOTE_CHARS {
+		return Err(ServerFnError::new(format!("Notes are at most {} characters", MAX_NOTE_CHARS)));
+	}
+	let tags = parse_tags(&tags);
+	if tags.len() > MAX_TAGS || tags.iter().any(|tag| tag.chars().count() > MAX_TAG_CHARS) {
+		return Err(ServerFnError::new(format!("Up to {} tags of at most {} characters", MAX_TAGS, MAX_TAG_CHARS)));
+	}
+
+	repository::upsert_bookmark(&state.db_pool, crate::auth::current_user_id(), article_id, note, &tags).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	Ok(())
+}
+
+#[server]
+pub async fn remove_bookmark(article_id: i64) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	repository::delete_bookmark(&state.db_pool, crate::auth::current_user_id(), article_id).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	Ok(())
+}
+
+#[server]
+pub async fn get_bookmarks(persona: String) -> Result<Vec<SavedArticle>, ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	let persona = super::personas::resolve_persona(persona);
+	let mut bookmarks = repository::get_bookmarks(&state.db_pool, crate::auth::current_user_id(), &persona).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+	let ids: Vec<i64> = bookmarks.iter().map(|b| b.article.id).collect();
+	let mut histories = repository::get_score_histories(&state.db_pool, &ids, repository::SCORE_HISTORY_POINTS).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	for bookmark in &mut bookmarks {
+		bookmark.article.score_history = histories.remove(&bookmark.article.id).unwrap_or_default();
+	}
+
+	Ok(bookmarks)
+}
+
+// "Rust, databases,rust" -> ["rust", "databases"]
+#[cfg(feature = "ssr")]
+fn parse_tags(tags: &str) -> Vec<String> {
+	let mut parsed: Vec<String> = Vec::new();
+	for tag in tags.split(',').map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()) {
+		if !parsed.contains(&tag) {
+			parsed.push(tag);
+		}
+	}
+	parsed
+}
@@ -1 +1,6 @@
 pub mod articles;
+pub mod auth;
+pub mod bookmarks;
+pub mod personas;
+pub mod runs;
+pub mod status;
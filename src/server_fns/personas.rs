@@ -0,0 +1,38 @@
+use crate::db::models::Persona;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::state::AppState;
+
+#[server]
+pub async fn get_personas() -> Result<Vec<Persona>, ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	repository::get_personas(&state.db_pool).await.map_err(|e| ServerFnError::ServerError(e.to_string()))
+}
+
+// Remember the signed-in user's persona; a no-op when signed out
+#[server]
+pub async fn set_persona(persona: String) -> Result<(), ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	if let Some(user) = crate::auth::current_user() {
+		repository::set_user_persona(&state.db_pool, user.id, &persona).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	}
+
+	Ok(())
+}
+
+// An empty persona means the signed-in user's preferred one, or the default
+#[cfg(feature = "ssr")]
+pub fn resolve_persona(persona: String) -> String {
+	use crate::db::models::DEFAULT_PERSONA;
+
+	if !persona.is_empty() {
+		return persona;
+	}
+
+	crate::auth::current_user().and_then(|user| user.persona).unwrap_or_else(|| DEFAULT_PERSONA.to_string())
+}
@@ -0,0 +1,13 @@
+use crate::db::models::AggregatorRun;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::state::AppState;
+
+#[server]
+pub async fn get_recent_runs(limit: i64, include_idle: bool) -> Result<Vec<AggregatorRun>, ServerFnError> {
+	use crate::db::repository;
+	let state = expect_context::<AppState>();
+
+	repository::get_recent_runs(&state.db_pool, limit.clamp(1, 500), include_idle).await.map_err(|e| ServerFnError::ServerError(e.to_string()))
+}
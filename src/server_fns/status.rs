@@ -0,0 +1,70 @@
+use crate::db::models::{AggregatorStatus, BreakerStatus};
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::state::AppState;
+
+#[server]
+pub async fn get_analyzer_status() -> Result<BreakerStatus, ServerFnError> {
+	let state = expect_context::<AppState>();
+
+	Ok(state.analyzer_breaker.status())
+}
+
+// Stable URL (POST /api/aggregator_status) for scripts and monitoring
+#[server(endpoint = "aggregator_status")]
+pub async fn get_aggregator_status() -> Result<AggregatorStatus, ServerFnError> {
+	let state = expect_context::<AppState>();
+
+	Ok(state.aggregator.status())
+}
+
+// Ask the fetch worker for an immediate cycle (POST /api/refresh, admins only); returns false if one is already running
+#[server(endpoint = "refresh")]
+pub async fn trigger_refresh() -> Result<bool, ServerFnError> {
+	let state = expect_context::<AppState>();
+
+	if !crate::auth::is_admin() {
+		return Err(ServerFnError::new("Only admins can trigger a refresh"));
+	}
+
+	if state.aggregator.fetch.is_running() {
+		return Ok(false);
+	}
+
+	state.aggregator.refresh_requested.notify_one();
+	Ok(true)
+}
+
+// Re-queue analyses made with an older persona, category list, model or prompt (POST /api/requeue_stale, admins only),
+// optionally only for stories posted in the last `days` days; returns how many were queued
+#[server(endpoint = "requeue_stale")]
+pub async fn requeue_stale_analyses(days: Option<i64>) -> Result<u64, ServerFnError> {
+	use crate::db::repository;
+	use crate::services::analyzer;
+	let state = expect_context::<AppState>();
+
+	if !crate::auth::is_admin() {
+		return Err(ServerFnError::new("Only admins can re-queue analyses"));
+	}
+	if days.is_some_and(|days| !(1..=3650).contains(&days)) {
+		return Err(ServerFnError::new("days must be between 1 and 3650"));
+	}
+
+	let personas = repository::get_personas(&state.db_pool).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+	let mut queued = 0;
+	for persona in personas {
+		let stamp = analyzer::analysis_stamp(&persona.description, &state.config.categories, &state.analyzer.model());
+		let count = repository::requeue_stale_analyses(&state.db_pool, persona.id, &stamp, days).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+		if count > 0 {
+			tracing::info!("Re-queued {} stale analyses for '{}'", count, persona.name);
+		}
+		queued += count;
+	}
+
+	if queued > 0 {
+		state.aggregator.analysis_wakeup.notify_one();
+	}
+
+	Ok(queued)
+}
@@ -1,106 +1,143 @@
 #[cfg(feature = "ssr")]
-use std::sync::Arc;
+use std::{
+	sync::{Arc, Mutex},
+	time::{SystemTime, UNIX_EPOCH},
+};
 
 #[cfg(feature = "ssr")]
 use anyhow::Result;
 
 #[cfg(feature = "ssr")]
-use tokio::time::{interval, Duration};
+use tokio::{
+	sync::Notify,
+	time::{Duration, Instant},
+};
+
+#[cfg(feature = "ssr")]
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "ssr")]
 use crate::config::Config;
 
 #[cfg(feature = "ssr")]
-use crate::db::repository;
+use crate::db::{
+	models::{AggregatorStatus, WorkerRunStatus},
+	repository,
+};
+
+#[cfg(feature = "ssr")]
+use crate::services::{
+	analysis_worker::AnalysisWorker, analyzer::Analyzer, circuit_breaker::CircuitBreaker, content_extractor::ContentExtractor, fetch_worker::FetchWorker, hn_client::HnClient, retry::RetryPolicy,
+};
 
+// Shared between the workers and the server functions: run tracking and manual triggers
 #[cfg(feature = "ssr")]
-use crate::services::{hn_client, ollama_client};
+#[derive(Default)]
+pub struct AggregatorControl {
+	pub fetch: WorkerTracker,
+	pub analysis: WorkerTracker,
+	// Asks the fetch worker for an immediate cycle
+	pub refresh_requested: Notify,
+	// Wakes the analysis worker as soon as new stories are saved
+	pub analysis_wakeup: Notify,
+}
 
 #[cfg(feature = "ssr")]
-pub async fn run_aggregator_loop(db_pool: sqlx::SqlitePool, config: Arc<Config>) -> Result<()> {
-	// Run immediately on startup
-	tracing::info!("Running initial aggregator cycle...");
-	if let Err(e) = fetch_and_analyze_cycle(&db_pool, &config).await {
-		tracing::error!("Initial aggregator cycle failed: {}", e);
+impl AggregatorControl {
+	pub fn status(&self) -> AggregatorStatus {
+		AggregatorStatus { fetch: self.fetch.snapshot(), analysis: self.analysis.snapshot() }
 	}
+}
 
-	// Then run every N minutes (from config)
-	let mut interval = interval(Duration::from_secs(config.fetch_interval_minutes * 60));
-	interval.tick().await; // Skip first tick (already ran above)
+// Last run and next scheduled run of one worker
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+pub struct WorkerTracker {
+	inner: Mutex<TrackerState>,
+}
 
-	loop {
-		interval.tick().await;
+#[cfg(feature = "ssr")]
+#[derive(Default)]
+struct TrackerState {
+	running: bool,
+	last_started: Option<SystemTime>,
+	last_duration: Option<Duration>,
+	processed: usize,
+	failed: usize,
+	next_run: Option<Instant>,
+}
 
-		tracing::info!("Starting aggregator cycle...");
-		if let Err(e) = fetch_and_analyze_cycle(&db_pool, &config).await {
-			tracing::error!("Aggregator cycle failed: {}", e);
+#[cfg(feature = "ssr")]
+impl WorkerTracker {
+	pub fn is_running(&self) -> bool {
+		self.inner.lock().unwrap().running
+	}
+
+	pub fn start(&self) {
+		let mut state = self.inner.lock().unwrap();
+		state.running = true;
+		state.last_started = Some(SystemTime::now());
+		state.next_run = None;
+	}
+
+	pub fn finish(&self, processed: usize, failed: usize) {
+		let mut state = self.inner.lock().unwrap();
+		state.running = false;
+		state.last_duration = state.last_started.and_then(|t| t.elapsed().ok());
+		state.processed = processed;
+		state.failed = failed;
+	}
+
+	pub fn schedule_next(&self, at: Instant) {
+		self.inner.lock().unwrap().next_run = Some(at);
+	}
+
+	pub fn snapshot(&self) -> WorkerRunStatus {
+		let state = self.inner.lock().unwrap();
+
+		WorkerRunStatus {
+			running: state.running,
+			last_run_at: state.last_started.and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map(|d| d.as_secs() as i64),
+			last_run_secs_ago: state.last_started.and_then(|t| t.elapsed().ok()).map(|d| d.as_secs()),
+			last_duration_ms: state.last_duration.map(|d| d.as_millis() as u64),
+			processed: state.processed,
+			failed: state.failed,
+			next_run_in_secs: state.next_run.map(|t| t.saturating_duration_since(Instant::now()).as_secs()),
 		}
 	}
 }
 
+// Run the fetch and analysis workers until `shutdown` is cancelled and both have stopped
 #[cfg(feature = "ssr")]
-async fn fetch_and_analyze_cycle(db_pool: &sqlx::SqlitePool, config: &Config) -> Result<()> {
-	// Step 1: Fetch top N HN stories (from config)
-	tracing::info!("Fetching top {} HN stories...", config.top_stories_count);
-	let story_ids = hn_client::fetch_top_stories(config.top_stories_count).await?;
-	tracing::info!("Fetched {} story IDs", story_ids.len());
-
-	// Step 2: Get details and save to database
-	for id in story_ids {
-		// Small delay to avoid rate limiting
-		tokio::time::sleep(Duration::from_millis(100)).await;
-
-		match hn_client::fetch_item(id).await {
-			Ok(item) => {
-				if let Err(e) = repository::upsert_article(db_pool, &item).await {
-					tracing::warn!("Failed to save article {}: {}", id, e);
-				}
-			}
-			Err(e) => {
-				tracing::warn!("Failed to fetch item {}: {}", id, e);
-			}
-		}
+pub async fn run_workers(
+	db_pool: sqlx::SqlitePool,
+	config: Arc<Config>,
+	analyzer: Arc<dyn Analyzer>,
+	analyzer_breaker: Arc<CircuitBreaker>,
+	control: Arc<AggregatorControl>,
+	shutdown: CancellationToken,
+) -> Result<()> {
+	let reset = repository::reset_running_jobs(&db_pool).await?;
+	if reset > 0 {
+		tracing::info!("Re-queued {} analysis jobs interrupted by the last shutdown", reset);
 	}
 
-	// Step 3: Get unanalyzed articles
-	let articles = repository::get_unanalyzed_articles(db_pool).await?;
-	tracing::info!("Found {} unanalyzed articles", articles.len());
-
-	// Step 4: Analyze with Ollama (sequential to avoid overwhelming local Ollama)
-	for article in articles {
-		match ollama_client::analyze_article(&config.persona, &article, &config.categories, &config.ollama_url, &config.ollama_model).await {
-			Ok(mut analysis) => {
-				// Validate category - if not in list, force to "Other"
-				if !config.categories.iter().any(|c| c.eq_ignore_ascii_case(&analysis.category)) {
-					tracing::warn!(
-						"LLM returned invalid category '{}' for article '{}', using 'Other'",
-						analysis.category,
-						article.title
-					);
-					analysis.category = "Other".to_string();
-				}
-
-				tracing::info!(
-					"Article '{}' analyzed: relevant={}, priority={}, category={}",
-					article.title,
-					analysis.relevant,
-					analysis.priority,
-					analysis.category
-				);
-
-				if let Err(e) = repository::update_analysis(db_pool, article.id, analysis).await {
-					tracing::error!("Failed to save analysis for article {}: {}", article.id, e);
-				}
-			}
-			Err(e) => {
-				tracing::warn!("Failed to analyze article {}: {}", article.hn_id, e);
-			}
-		}
+	// One client for both workers so they share the HN rate limit
+	let hn = HnClient::new(&config)?;
+	let fetch_worker = FetchWorker { db_pool: db_pool.clone(), config: config.clone(), hn: hn.clone(), control: control.clone() };
 
-		// Small delay between analyses
-		tokio::time::sleep(Duration::from_millis(500)).await;
-	}
+	let analysis_worker = AnalysisWorker {
+		hn,
+		extractor: ContentExtractor::new()?,
+		retry: RetryPolicy::from_config(&config),
+		analyzer,
+		analyzer_breaker,
+		control,
+		db_pool,
+		config,
+	};
+
+	tokio::join!(fetch_worker.run(shutdown.clone()), analysis_worker.run(shutdown));
 
-	tracing::info!("Aggregator cycle completed");
 	Ok(())
 }
@@ -0,0 +1,361 @@
+#[cfg(feature = "ssr")]
+use std::{collections::HashMap, sync::Arc};
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+#[cfg(feature = "ssr")]
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "ssr")]
+use crate::config::Config;
+
+#[cfg(feature = "ssr")]
+use crate::db::{
+	models::{FeedbackExample, JobStatus, RunMetrics},
+	repository,
+};
+
+#[cfg(feature = "ssr")]
+use crate::metrics;
+
+#[cfg(feature = "ssr")]
+use crate::services::{
+	aggregator::AggregatorControl,
+	analyzer::{self, Analyzer},
+	circuit_breaker::CircuitBreaker,
+	content_extractor::{self, ContentExtractor},
+	hn_client::HnClient,
+	retry::{self, RetryPolicy},
+};
+
+// Keeps each cycle short, remaining discussions are picked up by the next ones
+#[cfg(feature = "ssr")]
+const MAX_SUMMARIES_PER_CYCLE: i64 = 5;
+
+// Keeps the summary prompt within small context windows
+#[cfg(feature = "ssr")]
+const MAX_COMMENT_CHARS: usize = 600;
+
+// Works through the analysis queue, independently of HN fetching
+#[cfg(feature = "ssr")]
+pub struct AnalysisWorker {
+	pub db_pool: sqlx::SqlitePool,
+	pub config: Arc<Config>,
+	pub analyzer: Arc<dyn Analyzer>,
+	pub hn: HnClient,
+	pub extractor: ContentExtractor,
+	pub retry: RetryPolicy,
+	pub analyzer_breaker: Arc<CircuitBreaker>,
+	pub control: Arc<AggregatorControl>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Default)]
+struct AnalysisStats {
+	due: usize,
+	analyzed: usize,
+	rejected: usize,
+	summarized: usize,
+	llm_errors: usize,
+	llm_calls: u32,
+	llm_time: Duration,
+}
+
+#[cfg(feature = "ssr")]
+impl AnalysisWorker {
+	pub async fn run(self, shutdown: CancellationToken) {
+		// Runs on its own schedule, or right away when the fetch worker saved new stories
+		let period = Duration::from_secs(self.config.analysis_interval_minutes * 60);
+		let mut interval = interval(period);
+		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		self.control.analysis.schedule_next(Instant::now());
+
+		loop {
+			tokio::select! {
+				_ = shutdown.cancelled() => break,
+				_ = interval.tick() => {}
+				_ = self.control.analysis_wakeup.notified() => {}
+			}
+
+			tracing::info!("Starting analysis cycle...");
+			self.control.analysis.start();
+			let started = Instant::now();
+			let run_id = repository::start_run(&self.db_pool, "analysis").await.inspect_err(|e| tracing::warn!("Failed to record analysis run: {}", e)).ok();
+
+			let mut metrics = RunMetrics::default();
+			match self.analysis_cycle(&shutdown).await {
+				Ok(stats) => {
+					metrics.idle = stats.due == 0 && stats.summarized == 0;
+					tracing::info!(
+						"Analysis cycle completed, {} articles analyzed ({} rejected), {} discussions summarized, {} LLM errors",
+						stats.analyzed,
+						stats.rejected,
+						stats.summarized,
+						stats.llm_errors
+					);
+					self.control.analysis.finish(stats.analyzed, stats.llm_errors);
+					metrics.analyzed = stats.analyzed as i64;
+					metrics.rejected = stats.rejected as i64;
+					metrics.llm_errors = stats.llm_errors as i64;
+					metrics.avg_llm_latency_ms = (stats.llm_calls > 0).then(|| (stats.llm_time / stats.llm_calls).as_millis() as i64);
+				}
+				Err(e) => {
+					tracing::error!("Analysis cycle failed: {}", e);
+					self.control.analysis.finish(0, 1);
+					metrics.error = Some(format!("{:#}", e));
+				}
+			}
+
+			if let Some(run_id) = run_id {
+				metrics.duration_ms = started.elapsed().as_millis() as i64;
+				if let Err(e) = repository::finish_run(&self.db_pool, run_id, &metrics).await {
+					tracing::warn!("Failed to record analysis run: {}", e);
+				}
+			}
+
+			interval.reset();
+			self.control.analysis.schedule_next(Instant::now() + period);
+		}
+
+		tracing::info!("Analysis worker stopped");
+	}
+
+	async fn analysis_cycle(&self, shutdown: &CancellationToken) -> Result<AnalysisStats> {
+		let db_pool = &self.db_pool;
+		let config = self.config.as_ref();
+
+		// Step 1: Get due analysis jobs
+		let jobs = repository::get_due_analysis_jobs(db_pool, config.exclude_job_posts).await?;
+		tracing::info!("Found {} articles awaiting analysis", jobs.len());
+
+		// Step 2: Analyze with the configured provider (sequential to avoid overwhelming local inference servers)
+		let mut stats = AnalysisStats { due: jobs.len(), ..Default::default() };
+		let mut fetched_content: HashMap<i64, Option<String>> = HashMap::new();
+		let mut feedback_examples: HashMap<i64, Vec<FeedbackExample>> = HashMap::new();
+		for job in jobs {
+			// Shutdown is only honoured between articles so an in-flight analysis always completes
+			if shutdown.is_cancelled() {
+				tracing::info!("Shutdown requested, leaving remaining jobs queued");
+				break;
+			}
+
+			// Don't hammer a provider that is down, the remaining jobs wait for the next cycle
+			if !self.analyzer_breaker.allow() {
+				tracing::warn!("Analyzer circuit breaker is open, pausing analysis until the next cycle");
+				break;
+			}
+
+			let mut article = job.article;
+			// Left queued for the next cycle, one bad row shouldn't stop the others
+			if let Err(e) = repository::start_analysis_job(db_pool, job.job_id).await {
+				tracing::error!("Failed to start analysis job for article {}: {}", article.hn_id, e);
+				// The analyzer wasn't called, a half-open probe slot goes to the next job
+				self.analyzer_breaker.release();
+				continue;
+			}
+
+			// Every persona has its own job for the article, only download the page once per cycle
+			if let Some(content) = fetched_content.get(&article.id) {
+				article.content = content.clone();
+			} else if config.fetch_article_content && article.content.is_none() {
+				if let Some(url) = article.url.clone() {
+					match self.extractor.fetch_text(&url).await {
+						Ok(text) if !text.is_empty() => {
+							if let Err(e) = repository::update_content(db_pool, article.id, &text).await {
+								tracing::warn!("Failed to save content for article {}: {}", article.id, e);
+							}
+							article.content = Some(text);
+						}
+						Ok(_) => tracing::debug!("No readable content at {}", url),
+						Err(e) => tracing::warn!("Failed to extract content from {}: {}", url, e),
+					}
+				}
+				fetched_content.insert(article.id, article.content.clone());
+			}
+
+			// Only an excerpt goes into the prompt to keep it within small context windows
+			article.content = article.content.map(|c| content_extractor::truncate_chars(&c, config.content_excerpt_chars));
+
+			// Reader votes for the persona, loaded once per cycle; an article is never its own example
+			if config.feedback_examples > 0 && !feedback_examples.contains_key(&job.persona_id) {
+				let examples = repository::get_feedback_examples(db_pool, job.persona_id, config.feedback_examples).await.unwrap_or_else(|e| {
+					tracing::warn!("Failed to load feedback examples for '{}': {}", job.persona_name, e);
+					Vec::new()
+				});
+				feedback_examples.insert(job.persona_id, examples);
+			}
+			let examples: Vec<FeedbackExample> = feedback_examples.get(&job.persona_id).into_iter().flatten().filter(|e| e.article_id != article.id).cloned().collect();
+
+			// Latency covers the whole call, retries included
+			let llm_started = Instant::now();
+			let result = self.retry.run("Article analysis", || self.analyzer.analyze(&job.persona, &article, &config.categories, &examples)).await;
+			let llm_elapsed = llm_started.elapsed();
+			stats.llm_calls += 1;
+			stats.llm_time += llm_elapsed;
+			metrics::record_analyzer_request(self.analyzer.name(), result.is_ok(), llm_elapsed);
+			// Only outages count against the provider, a bad answer still means it is up
+			match &result {
+				Err(e) if retry::is_transient(e) => self.analyzer_breaker.record_failure(),
+				_ => self.analyzer_breaker.record_success(),
+			}
+
+			match result {
+				Ok(mut analysis) => {
+					// Validate category - if not in list, force to "Other"
+					if !config.categories.iter().any(|c| c.eq_ignore_ascii_case(&analysis.category)) {
+						tracing::warn!(
+							"LLM returned invalid category '{}' for article '{}', using 'Other'",
+							analysis.category,
+							article.title
+						);
+						analysis.category = "Other".to_string();
+						metrics::record_invalid_category();
+					}
+
+					tracing::info!(
+						"Article '{}' analyzed for '{}': relevant={}, priority={}, category={}",
+						article.title,
+						job.persona_name,
+						analysis.relevant,
+						analysis.priority,
+						analysis.category
+					);
+
+					let relevant = analysis.relevant;
+					let category = analysis.category.clone();
+					let stamp = analyzer::analysis_stamp(&job.persona, &config.categories, &self.analyzer.model());
+					match repository::update_analysis(db_pool, article.id, job.persona_id, &stamp, analysis).await {
+						Ok(()) => {
+							metrics::record_category(&category);
+							stats.analyzed += 1;
+							if !relevant {
+								stats.rejected += 1;
+							}
+						}
+						Err(e) => {
+							tracing::error!("Failed to save analysis for article {}: {}", article.hn_id, e);
+							self.fail_job(job.job_id, job.attempts + 1, article.hn_id, &e).await;
+						}
+					}
+				}
+				Err(e) => {
+					tracing::warn!("Failed to analyze article {}: {}", article.hn_id, e);
+					stats.llm_errors += 1;
+					self.fail_job(job.job_id, job.attempts + 1, article.hn_id, &e).await;
+				}
+			}
+
+			// Small delay between analyses
+			tokio::select! {
+				_ = shutdown.cancelled() => {}
+				_ = tokio::time::sleep(Duration::from_millis(500)) => {}
+			}
+		}
+
+		// Step 3: Summarize the HN discussion of high-priority stories
+		if self.analyzer.supports_summaries() {
+			self.summarize_discussions(shutdown, &mut stats).await?;
+		}
+
+		Ok(stats)
+	}
+
+	// Back off exponentially between attempts: delay, 2x delay, 4x delay...
+	async fn fail_job(&self, job_id: i64, attempts: i64, hn_id: i64, error: &anyhow::Error) {
+		let delay = self.config.analysis_retry_delay_minutes.saturating_mul(1 << (attempts - 1).clamp(0, 10));
+		match repository::fail_analysis_job(&self.db_pool, job_id, &format!("{:#}", error), self.config.analysis_max_attempts, delay).await {
+			Ok(JobStatus::Dead) => tracing::error!("Giving up on article {} after {} attempts", hn_id, attempts),
+			Ok(_) => {}
+			Err(e) => tracing::error!("Failed to record analysis failure for article {}: {}", hn_id, e),
+		}
+	}
+
+	async fn summarize_discussions(&self, shutdown: &CancellationToken, stats: &mut AnalysisStats) -> Result<()> {
+		let db_pool = &self.db_pool;
+		let config = self.config.as_ref();
+
+		let articles = repository::get_discussions_to_summarize(db_pool, config.comment_summary_min_priority, config.comment_summary_min_comments, config.analysis_max_attempts, MAX_SUMMARIES_PER_CYCLE).await?;
+		for article in articles {
+			if shutdown.is_cancelled() {
+				break;
+			}
+
+			let comments = self.fetch_comments(&article.kids).await;
+			if comments.is_empty() {
+				tracing::debug!("No readable comments on article {}", article.hn_id);
+				self.fail_summary(article.id).await;
+				continue;
+			}
+
+			if !self.analyzer_breaker.allow() {
+				break;
+			}
+
+			let llm_started = Instant::now();
+			let result = self.retry.run("Discussion summary", || self.analyzer.summarize_discussion(&article, &comments)).await;
+			let llm_elapsed = llm_started.elapsed();
+			stats.llm_calls += 1;
+			stats.llm_time += llm_elapsed;
+			metrics::record_analyzer_request(self.analyzer.name(), result.is_ok(), llm_elapsed);
+			match &result {
+				Err(e) if retry::is_transient(e) => self.analyzer_breaker.record_failure(),
+				_ => self.analyzer_breaker.record_success(),
+			}
+
+			match result {
+				Ok(summary) if !summary.is_empty() => {
+					tracing::info!("Discussion of '{}' summarized from {} comments", article.title, comments.len());
+					match repository::update_discussion_summary(db_pool, article.id, &summary, article.descendants.unwrap_or(0)).await {
+						Ok(()) => stats.summarized += 1,
+						Err(e) => {
+							tracing::error!("Failed to save discussion summary for article {}: {}", article.hn_id, e);
+							self.fail_summary(article.id).await;
+						}
+					}
+				}
+				Ok(_) => {
+					tracing::warn!("Empty discussion summary for article {}", article.hn_id);
+					self.fail_summary(article.id).await;
+				}
+				Err(e) => {
+					tracing::warn!("Failed to summarize discussion of article {}: {}", article.hn_id, e);
+					stats.llm_errors += 1;
+					self.fail_summary(article.id).await;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn fail_summary(&self, article_id: i64) {
+		if let Err(e) = repository::fail_discussion_summary(&self.db_pool, article_id, self.config.analysis_retry_delay_minutes).await {
+			tracing::error!("Failed to record discussion summary attempt for article {}: {}", article_id, e);
+		}
+	}
+
+	// Top-level comments as "author: text", in HN's ranking order
+	async fn fetch_comments(&self, kids: &[i64]) -> Vec<String> {
+		let ids: Vec<i64> = kids.iter().copied().take(self.config.comment_summary_max_comments).collect();
+		let rank: HashMap<i64, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+		// Ranked by the id asked for, the body's id is whatever the API sent back
+		let mut items: Vec<_> = self.hn.fetch_items(ids).await.into_iter().filter_map(|(id, result)| result.inspect_err(|e| tracing::debug!("Failed to fetch comment {}: {}", id, e)).ok().map(|item| (id, item))).collect();
+		items.sort_by_key(|(id, _)| rank[id]);
+
+		items
+			.into_iter()
+			.map(|(_, item)| item)
+			.filter(|item| !item.dead && !item.deleted)
+			.filter_map(|item| {
+				let text = content_extractor::html_to_text(item.text.as_deref()?);
+				let author = item.by.unwrap_or_else(|| "anonymous".to_string());
+				Some(format!("{}: {}", author, content_extractor::truncate_chars(&text, MAX_COMMENT_CHARS)))
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,238 @@
+#[cfg(feature = "ssr")]
+use std::sync::Arc;
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use crate::config::{AnalyzerProvider, Config};
+
+#[cfg(feature = "ssr")]
+use crate::db::models::{AnalysisResult, Article, FeedbackExample};
+
+#[cfg(feature = "ssr")]
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "ssr")]
+use crate::services::{ollama_client::OllamaAnalyzer, openai_client::OpenAiAnalyzer, rule_based::RuleBasedAnalyzer};
+
+// Bump when the analysis prompt changes enough that existing analyses should be redone
+#[cfg(feature = "ssr")]
+pub const PROMPT_VERSION: u32 = 1;
+
+// Common interface for everything that can classify an article against a persona
+#[cfg(feature = "ssr")]
+#[async_trait::async_trait]
+pub trait Analyzer: Send + Sync {
+	/// Short identifier used in logs (e.g. "ollama", "openai", "rule-based")
+	fn name(&self) -> &'static str;
+
+	/// Provider and model, part of the analysis stamp so switching models marks analyses stale
+	fn model(&self) -> String {
+		self.name().to_string()
+	}
+
+	/// `examples` are articles readers voted on for this persona, to steer the classification
+	async fn analyze(&self, persona: &str, article: &Article, categories: &[String], examples: &[FeedbackExample]) -> Result<AnalysisResult>;
+
+	/// Whether `summarize_discussion` is implemented, so callers can skip fetching comments otherwise
+	fn supports_summaries(&self) -> bool {
+		false
+	}
+
+	/// Short plain-text summary of an HN discussion from its top-level comments
+	async fn summarize_discussion(&self, _article: &Article, _comments: &[String]) -> Result<String> {
+		anyhow::bail!("The '{}' analyzer cannot summarize discussions", self.name())
+	}
+
+	/// Cheap probe used by the readiness endpoint; providers without an upstream are always ready
+	async fn health_check(&self) -> Result<()> {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "ssr")]
+pub fn from_config(config: &Config) -> Result<Arc<dyn Analyzer>> {
+	let analyzer: Arc<dyn Analyzer> = match config.analyzer_provider {
+		AnalyzerProvider::Ollama => Arc::new(OllamaAnalyzer::new(&config.ollama_url, &config.ollama_model)?),
+		AnalyzerProvider::OpenAi => Arc::new(OpenAiAnalyzer::new(&config.openai_url, &config.openai_model, config.openai_api_key.as_deref())?),
+		AnalyzerProvider::RuleBased => Arc::new(RuleBasedAnalyzer),
+	};
+
+	Ok(analyzer)
+}
+
+// Identifies what an analysis was made with; analyses with another stamp are stale.
+// Feedback examples are left out, they change with every vote.
+#[cfg(feature = "ssr")]
+pub fn analysis_stamp(persona: &str, categories: &[String], model: &str) -> String {
+	let mut hasher = Sha256::new();
+	for part in [persona.trim(), &categories.join("\n"), model, &PROMPT_VERSION.to_string()] {
+		hasher.update(part.as_bytes());
+		hasher.update([0]);
+	}
+	hasher.finalize().iter().take(8).map(|b| format!("{:02x}", b)).collect()
+}
+
+// Prompt shared by the LLM-backed analyzers
+#[cfg(feature = "ssr")]
+pub fn build_prompt(persona: &str, article: &Article, categories: &[String], examples: &[FeedbackExample]) -> String {
+	let categories_str = categories.join(", ");
+	let examples_str = build_examples(examples);
+
+	let mut prompt = format!(
+		r#"Analyze this Hacker News article.
+Assign the most specific category from the list below.
+Use 'Other' ONLY for news that does not fit any other category.
+
+Output Format (JSON):
+{{"relevant": boolean, "reason": "explanation", "priority": number (1-5), "category": "category_name", "summary": "what the article is about" or null, "key_points": ["point", ...]}}
+
+When the article content is provided, fill 'summary' (1 to 2 sentences) and 'key_points' (2 to 4 short points) from it; otherwise use null and [].
+
+Persona: {}
+{}
+Available Categories (Strict): {}
+
+Article Title: {}
+Article URL: {}"#,
+		persona,
+		examples_str,
+		categories_str,
+		article.title,
+		article.url.as_deref().unwrap_or("N/A")
+	);
+
+	if let Some(content) = article.content.as_deref().filter(|c| !c.is_empty()) {
+		prompt.push_str("\n\nArticle Content (excerpt):\n");
+		prompt.push_str(content);
+	}
+
+	prompt
+}
+
+// Few-shot section from reader feedback, empty when nobody voted yet
+#[cfg(feature = "ssr")]
+fn build_examples(examples: &[FeedbackExample]) -> String {
+	if examples.is_empty() {
+		return String::new();
+	}
+
+	let titles = |relevant: bool| examples.iter().filter(|e| e.relevant == relevant).map(|e| format!("- {}", e.title)).collect::<Vec<_>>();
+	let mut section = String::from("\nThe reader corrected earlier classifications, judge similar articles the same way.\n");
+	for (label, titles) in [("Relevant", titles(true)), ("Not relevant", titles(false))] {
+		if !titles.is_empty() {
+			section.push_str(&format!("{}:\n{}\n", label, titles.join("\n")));
+		}
+	}
+
+	section
+}
+
+// Prompt asking for a plain-text digest of the top-level comments
+#[cfg(feature = "ssr")]
+pub fn build_discussion_prompt(article: &Article, comments: &[String]) -> String {
+	format!(
+		r#"Summarize this Hacker News discussion in 3 to 5 short sentences.
+Cover the main arguments, notable disagreement and any useful links or first-hand experience.
+Answer in plain text, without a preamble.
+
+Article Title: {}
+
+Top-level comments:
+{}"#,
+		article.title,
+		comments.iter().map(|c| format!("- {}", c)).collect::<Vec<_>>().join("\n")
+	)
+}
+
+// Parse the raw model output into an AnalysisResult
+#[cfg(feature = "ssr")]
+pub fn parse_analysis(content: &str) -> Result<AnalysisResult> {
+	// Try to extract JSON from response (in case there's extra text)
+	let json_str = extract_json(content);
+
+	// Log for debugging if parsing fails
+	let mut analysis: AnalysisResult = serde_json::from_str(&json_str).map_err(|e| {
+		tracing::error!("Failed to parse JSON. Error: {}. Raw content: {}", e, content);
+		anyhow::anyhow!("Failed to parse analysis JSON")
+	})?;
+
+	// Models sometimes answer "" or [""] instead of null and []
+	analysis.summary = analysis.summary.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+	analysis.key_points = analysis.key_points.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+
+	Ok(analysis)
+}
+
+#[cfg(feature = "ssr")]
+fn extract_json(content: &str) -> String {
+	// Try to find JSON object in the content
+	if let Some(start) = content.find('{') {
+		if let Some(end) = content.rfind('}') {
+			if start < end {
+				return content[start..=end].to_string();
+			}
+		}
+	}
+	// If no JSON found, return original content
+	content.to_string()
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_analysis_drops_blank_summary_and_key_points() {
+		let analysis = parse_analysis(r#"{"relevant": true, "reason": "fits", "priority": 4, "category": "Programming", "summary": "  ", "key_points": [" first ", "", "  "]}"#).unwrap();
+		assert_eq!(analysis.summary, None);
+		assert_eq!(analysis.key_points, vec!["first".to_string()]);
+
+		let analysis = parse_analysis(r#"{"relevant": false, "reason": "off topic", "priority": 1, "category": "Other", "summary": " A summary. "}"#).unwrap();
+		assert_eq!(analysis.summary.as_deref(), Some("A summary."));
+		assert!(analysis.key_points.is_empty());
+	}
+
+	#[test]
+	fn parse_analysis_finds_json_in_surrounding_text() {
+		let analysis = parse_analysis("Sure! Here it is:\n```json\n{\"relevant\": true, \"reason\": \"fits\", \"priority\": 5}\n```").unwrap();
+		assert!(analysis.relevant);
+		assert_eq!(analysis.priority, 5);
+		assert_eq!(analysis.category, "Other");
+	}
+
+	#[test]
+	fn parse_analysis_rejects_non_json() {
+		assert!(parse_analysis("I can't help with that").is_err());
+		assert!(parse_analysis(r#"{"relevant": "maybe"}"#).is_err());
+	}
+
+	fn categories() -> Vec<String> {
+		vec!["Programming".to_string(), "Other".to_string()]
+	}
+
+	#[test]
+	fn analysis_stamp_is_stable() {
+		let stamp = analysis_stamp("Rust and databases", &categories(), "llama3");
+		assert_eq!(stamp, analysis_stamp("Rust and databases", &categories(), "llama3"));
+		assert_eq!(stamp.len(), 16);
+		// Surrounding whitespace in persona files doesn't make analyses stale
+		assert_eq!(stamp, analysis_stamp("\n  Rust and databases \n", &categories(), "llama3"));
+	}
+
+	#[test]
+	fn analysis_stamp_changes_with_its_inputs() {
+		let stamp = analysis_stamp("Rust and databases", &categories(), "llama3");
+		assert_ne!(stamp, analysis_stamp("Rust and compilers", &categories(), "llama3"));
+		assert_ne!(stamp, analysis_stamp("Rust and databases", &["Programming".to_string()], "llama3"));
+		assert_ne!(stamp, analysis_stamp("Rust and databases", &["Other".to_string(), "Programming".to_string()], "llama3"));
+		assert_ne!(stamp, analysis_stamp("Rust and databases", &categories(), "mistral"));
+	}
+
+	#[test]
+	fn analysis_stamp_separates_its_parts() {
+		// Moving text from one part to the next must not produce the same hash
+		assert_ne!(analysis_stamp("ab", &categories(), "c"), analysis_stamp("a", &categories(), "bc"));
+	}
+}
@@ -0,0 +1,176 @@
+#[cfg(feature = "ssr")]
+use std::sync::Mutex;
+
+#[cfg(feature = "ssr")]
+use tokio::time::{Duration, Instant};
+
+#[cfg(feature = "ssr")]
+use crate::db::models::{BreakerState, BreakerStatus};
+
+// Stops calling an upstream after repeated failures, then lets one probe through after a cooldown
+#[cfg(feature = "ssr")]
+pub struct CircuitBreaker {
+	failure_threshold: u32,
+	cooldown: Duration,
+	inner: Mutex<Inner>,
+}
+
+#[cfg(feature = "ssr")]
+struct Inner {
+	state: BreakerState,
+	consecutive_failures: u32,
+	opened_at: Option<Instant>,
+	// Set while the single half-open probe runs; a probe nobody reported on is given up after a cooldown
+	probe_started_at: Option<Instant>,
+}
+
+#[cfg(feature = "ssr")]
+impl CircuitBreaker {
+	pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+		Self { failure_threshold: failure_threshold.max(1), cooldown, inner: Mutex::new(Inner { state: BreakerState::Closed, consecutive_failures: 0, opened_at: None, probe_started_at: None }) }
+	}
+
+	// Whether a call may go through right now; moves Open -> HalfOpen once the cooldown has elapsed, then admits one probe at a time
+	pub fn allow(&self) -> bool {
+		let mut inner = self.inner.lock().unwrap();
+		let admitted = match inner.state {
+			BreakerState::Closed => return true,
+			BreakerState::HalfOpen => inner.probe_started_at.is_none_or(|t| t.elapsed() >= self.cooldown),
+			BreakerState::Open => inner.opened_at.is_some_and(|t| t.elapsed() >= self.cooldown),
+		};
+		if admitted {
+			inner.state = BreakerState::HalfOpen;
+			inner.probe_started_at = Some(Instant::now());
+		}
+		admitted
+	}
+
+	// Hands back a call allow() admitted that never reached the upstream, so the next one can probe right away
+	pub fn release(&self) {
+		self.inner.lock().unwrap().probe_started_at = None;
+	}
+
+	pub fn record_success(&self) {
+		let mut inner = self.inner.lock().unwrap();
+		if inner.state != BreakerState::Closed {
+			tracing::info!("Circuit breaker closed, upstream recovered");
+		}
+		inner.state = BreakerState::Closed;
+		inner.consecutive_failures = 0;
+		inner.opened_at = None;
+		inner.probe_started_at = None;
+	}
+
+	pub fn record_failure(&self) {
+		let mut inner = self.inner.lock().unwrap();
+		inner.consecutive_failures += 1;
+		inner.probe_started_at = None;
+
+		// A failed probe re-opens immediately, otherwise wait for the threshold
+		if inner.state == BreakerState::HalfOpen || inner.consecutive_failures >= self.failure_threshold {
+			if inner.state != BreakerState::Open {
+				tracing::warn!("Circuit breaker opened after {} consecutive failures, pausing for {:?}", inner.consecutive_failures, self.cooldown);
+			}
+			inner.state = BreakerState::Open;
+			inner.opened_at = Some(Instant::now());
+		}
+	}
+
+	pub fn status(&self) -> BreakerStatus {
+		let inner = self.inner.lock().unwrap();
+		let retry_in_secs = match (inner.state, inner.opened_at) {
+			(BreakerState::Open, Some(opened_at)) => Some(self.cooldown.saturating_sub(opened_at.elapsed()).as_secs()),
+			_ => None,
+		};
+
+		BreakerStatus { state: inner.state, consecutive_failures: inner.consecutive_failures, retry_in_secs }
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	const COOLDOWN: Duration = Duration::from_secs(60);
+
+	#[tokio::test(start_paused = true)]
+	async fn opens_after_consecutive_failures() {
+		let breaker = CircuitBreaker::new(3, COOLDOWN);
+		breaker.record_failure();
+		breaker.record_failure();
+		assert!(breaker.allow());
+
+		breaker.record_failure();
+		assert_eq!(breaker.status().state, BreakerState::Open);
+		assert_eq!(breaker.status().retry_in_secs, Some(60));
+		assert!(!breaker.allow());
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn success_resets_the_failure_count() {
+		let breaker = CircuitBreaker::new(2, COOLDOWN);
+		breaker.record_failure();
+		breaker.record_success();
+		breaker.record_failure();
+		assert_eq!(breaker.status().state, BreakerState::Closed);
+		assert_eq!(breaker.status().consecutive_failures, 1);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn half_open_admits_a_single_probe() {
+		let breaker = CircuitBreaker::new(1, COOLDOWN);
+		breaker.record_failure();
+		tokio::time::advance(COOLDOWN).await;
+
+		assert!(breaker.allow());
+		assert_eq!(breaker.status().state, BreakerState::HalfOpen);
+		assert!(!breaker.allow());
+
+		breaker.record_success();
+		assert_eq!(breaker.status().state, BreakerState::Closed);
+		assert!(breaker.allow());
+		assert!(breaker.allow());
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn failed_probe_reopens() {
+		let breaker = CircuitBreaker::new(5, COOLDOWN);
+		for _ in 0..5 {
+			breaker.record_failure();
+		}
+		tokio::time::advance(COOLDOWN).await;
+
+		assert!(breaker.allow());
+		breaker.record_failure();
+		assert_eq!(breaker.status().state, BreakerState::Open);
+		assert!(!breaker.allow());
+
+		tokio::time::advance(COOLDOWN).await;
+		assert!(breaker.allow());
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn unreported_probe_is_replaced_after_a_cooldown() {
+		let breaker = CircuitBreaker::new(1, COOLDOWN);
+		breaker.record_failure();
+		tokio::time::advance(COOLDOWN).await;
+		assert!(breaker.allow());
+
+		tokio::time::advance(COOLDOWN / 2).await;
+		assert!(!breaker.allow());
+		tokio::time::advance(COOLDOWN / 2).await;
+		assert!(breaker.allow());
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn released_probe_is_admitted_again() {
+		let breaker = CircuitBreaker::new(1, COOLDOWN);
+		breaker.record_failure();
+		tokio::time::advance(COOLDOWN).await;
+		assert!(breaker.allow());
+
+		breaker.release();
+		assert_eq!(breaker.status().state, BreakerState::HalfOpen);
+		assert!(breaker.allow());
+	}
+}
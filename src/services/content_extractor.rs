@@ -0,0 +1,286 @@
+#[cfg(feature = "ssr")]
+use anyhow::{bail, Context, Result};
+
+// Stop downloading after this many bytes, articles rarely need more
+#[cfg(feature = "ssr")]
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+// Upper bound on the text kept in the database
+#[cfg(feature = "ssr")]
+pub const MAX_STORED_CHARS: usize = 20_000;
+
+// Tags whose content is never readable text
+#[cfg(feature = "ssr")]
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "noscript", "svg", "head", "template"];
+
+// Tags that start a new line when converted to text
+#[cfg(feature = "ssr")]
+const BLOCK_ELEMENTS: &[&str] = &["p", "div", "br", "li", "h1", "h2", "h3", "h4", "h5", "h6", "tr", "article", "section", "pre", "blockquote"];
+
+// Give up on pages that bounce around more than this
+#[cfg(feature = "ssr")]
+const MAX_REDIRECTS: usize = 5;
+
+// Downloads linked pages and reduces them to readable text
+#[cfg(feature = "ssr")]
+pub struct ContentExtractor {
+	client: reqwest::Client,
+	allow_private_hosts: bool,
+}
+
+#[cfg(feature = "ssr")]
+impl ContentExtractor {
+	pub fn new() -> Result<Self> {
+		Self::build(false)
+	}
+
+	// Article URLs are user submitted, so only public http(s) hosts are fetched, on every redirect hop too
+	fn build(allow_private_hosts: bool) -> Result<Self> {
+		let mut builder = reqwest::Client::builder()
+			.timeout(std::time::Duration::from_secs(15))
+			.user_agent(concat!("hn-smart-aggregator/", env!("CARGO_PKG_VERSION")))
+			.redirect(reqwest::redirect::Policy::custom(move |attempt| {
+				if attempt.previous().len() >= MAX_REDIRECTS {
+					attempt.error(format!("More than {} redirects", MAX_REDIRECTS))
+				} else if let Err(e) = check_url(attempt.url(), allow_private_hosts) {
+					attempt.error(e)
+				} else {
+					attempt.follow()
+				}
+			}));
+		if !allow_private_hosts {
+			builder = builder.dns_resolver(std::sync::Arc::new(PublicResolver));
+		}
+
+		Ok(Self { client: builder.build()?, allow_private_hosts })
+	}
+
+	pub async fn fetch_text(&self, url: &str) -> Result<String> {
+		let url = reqwest::Url::parse(url).context("Invalid article URL")?;
+		check_url(&url, self.allow_private_hosts)?;
+
+		let mut response = self.client.get(url).send().await.context("Failed to download article")?.error_for_status()?;
+
+		let content_type = response.headers().get(reqwest::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("text/html").to_ascii_lowercase();
+		let is_html = content_type.contains("html");
+		if !is_html && !content_type.starts_with("text/") {
+			bail!("Unsupported content type '{}'", content_type);
+		}
+
+		let mut body = Vec::new();
+		while let Some(chunk) = response.chunk().await? {
+			body.extend_from_slice(&chunk);
+			if body.len() >= MAX_BODY_BYTES {
+				body.truncate(MAX_BODY_BYTES);
+				break;
+			}
+		}
+
+		let raw = String::from_utf8_lossy(&body);
+		let text = if is_html { html_to_text(&raw) } else { collapse_whitespace(&raw) };
+
+		Ok(truncate_chars(&text, MAX_STORED_CHARS))
+	}
+}
+
+// Only http(s), and IP literals are checked here because they never reach the resolver
+#[cfg(feature = "ssr")]
+fn check_url(url: &reqwest::Url, allow_private_hosts: bool) -> Result<()> {
+	if !matches!(url.scheme(), "http" | "https") {
+		bail!("Refusing to fetch '{}' URLs", url.scheme());
+	}
+	let host = url.host_str().context("URL has no host")?;
+	if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse::<std::net::IpAddr>() {
+		if !allow_private_hosts && !is_public_ip(ip) {
+			bail!("Refusing to fetch from non-public address {}", ip);
+		}
+	}
+	Ok(())
+}
+
+// Loopback, private, link-local and other special-purpose ranges are off limits
+#[cfg(feature = "ssr")]
+fn is_public_ip(ip: std::net::IpAddr) -> bool {
+	match ip {
+		std::net::IpAddr::V4(ip) => {
+			let shared = ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64;
+			!(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() || ip.is_broadcast() || ip.is_multicast() || ip.is_documentation() || shared)
+		}
+		std::net::IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+			Some(v4) => is_public_ip(std::net::IpAddr::V4(v4)),
+			None => !(ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() || ip.is_unique_local() || ip.is_unicast_link_local()),
+		},
+	}
+}
+
+// Drops non-public addresses at connect time, which also covers hosts that resolve differently on a later lookup
+#[cfg(feature = "ssr")]
+struct PublicResolver;
+
+#[cfg(feature = "ssr")]
+impl reqwest::dns::Resolve for PublicResolver {
+	fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+		Box::pin(async move {
+			let addrs: Vec<std::net::SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.filter(|addr| is_public_ip(addr.ip())).collect();
+			if addrs.is_empty() {
+				return Err(format!("'{}' does not resolve to a public address", name.as_str()).into());
+			}
+			Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+		})
+	}
+}
+
+// Strip markup, scripts and styles, keeping paragraph breaks
+#[cfg(feature = "ssr")]
+pub fn html_to_text(html: &str) -> String {
+	let mut out = String::with_capacity(html.len() / 4);
+	let mut rest = html;
+
+	while let Some(start) = rest.find('<') {
+		out.push_str(&rest[..start]);
+		rest = &rest[start..];
+
+		// Comments can contain '>' so they need their own terminator
+		if rest.starts_with("<!--") {
+			rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+			continue;
+		}
+
+		let Some(end) = rest.find('>') else {
+			rest = "";
+			break;
+		};
+		let tag = &rest[1..end];
+		rest = &rest[end + 1..];
+
+		let name: String = tag.trim_start_matches('/').chars().take_while(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase();
+
+		if !tag.starts_with('/') && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+			let closing = format!("</{}", name);
+			rest = find_ignore_ascii_case(rest, &closing).map_or("", |pos| &rest[pos..]);
+			continue;
+		}
+
+		if BLOCK_ELEMENTS.contains(&name.as_str()) {
+			out.push('\n');
+		} else {
+			out.push(' ');
+		}
+	}
+	out.push_str(rest);
+
+	collapse_whitespace(&decode_entities(&out))
+}
+
+// Cut to at most `max` characters without splitting a UTF-8 sequence
+#[cfg(feature = "ssr")]
+pub fn truncate_chars(text: &str, max: usize) -> String {
+	match text.char_indices().nth(max) {
+		Some((idx, _)) => text[..idx].to_string(),
+		None => text.to_string(),
+	}
+}
+
+#[cfg(feature = "ssr")]
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+	haystack.as_bytes().windows(needle.len()).position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+#[cfg(feature = "ssr")]
+fn decode_entities(text: &str) -> String {
+	text.replace("&nbsp;", " ")
+		.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&#x27;", "'")
+		.replace("&#39;", "'")
+		.replace("&#x2F;", "/")
+		.replace("&amp;", "&")
+}
+
+// Collapse runs of spaces within lines and drop empty lines
+#[cfg(feature = "ssr")]
+fn collapse_whitespace(text: &str) -> String {
+	text.lines().map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|line| !line.is_empty()).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+	use axum::{http::header, response::Redirect, routing::get, Router};
+
+	const ARTICLE: &str = "<html><head><title>Ignored</title><style>p { color: red }</style></head><body><!-- a > b --><p>First &amp; foremost</p><script>alert('no')</script><p>Second\t line</p></body></html>";
+
+	// Serves the fixtures on an ephemeral local port
+	async fn fixture_server() -> String {
+		let app = Router::new()
+			.route("/article", get(|| async { ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], ARTICLE) }))
+			.route("/image", get(|| async { ([(header::CONTENT_TYPE, "image/png")], vec![0u8; 64]) }))
+			.route("/endless", get(|| async { ([(header::CONTENT_TYPE, "text/plain")], axum::body::Body::from_stream(futures::stream::repeat_with(|| Ok::<_, std::io::Error>(b"word ".repeat(1024))))) }))
+			.route("/moved", get(|| async { Redirect::temporary("/article") }))
+			.route("/loop", get(|| async { Redirect::temporary("/loop") }));
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+		format!("http://{}", addr)
+	}
+
+	#[test]
+	fn html_to_text_drops_markup_scripts_and_comments() {
+		assert_eq!(html_to_text(ARTICLE), "First & foremost\nSecond line");
+		assert_eq!(html_to_text("a<br>b<SCRIPT>x</SCRIPT>c &lt;d&gt;"), "a\nb c <d>");
+	}
+
+	#[test]
+	fn truncate_chars_keeps_utf8_intact() {
+		assert_eq!(truncate_chars("héllo", 2), "hé");
+		assert_eq!(truncate_chars("abc", 10), "abc");
+	}
+
+	#[tokio::test]
+	async fn fetch_text_extracts_html_and_follows_redirects() {
+		let base = fixture_server().await;
+		let extractor = ContentExtractor::build(true).unwrap();
+
+		assert_eq!(extractor.fetch_text(&format!("{}/article", base)).await.unwrap(), "First & foremost\nSecond line");
+		assert_eq!(extractor.fetch_text(&format!("{}/moved", base)).await.unwrap(), "First & foremost\nSecond line");
+		assert!(extractor.fetch_text(&format!("{}/loop", base)).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn fetch_text_rejects_binary_content() {
+		let base = fixture_server().await;
+		let err = ContentExtractor::build(true).unwrap().fetch_text(&format!("{}/image", base)).await.unwrap_err();
+		assert!(err.to_string().contains("Unsupported content type"), "{}", err);
+	}
+
+	#[tokio::test]
+	async fn fetch_text_stops_at_the_size_cap() {
+		let base = fixture_server().await;
+		let extractor = ContentExtractor::build(true).unwrap();
+
+		// The body never ends, so this only returns if the download is capped
+		let text = tokio::time::timeout(std::time::Duration::from_secs(10), extractor.fetch_text(&format!("{}/endless", base))).await.expect("download was not capped").unwrap();
+		assert_eq!(text.chars().count(), MAX_STORED_CHARS);
+	}
+
+	#[tokio::test]
+	async fn fetch_text_refuses_private_hosts_and_other_schemes() {
+		let base = fixture_server().await;
+		let extractor = ContentExtractor::new().unwrap();
+
+		for url in [format!("{}/article", base), "http://localhost/".to_string(), "http://[::1]/".to_string(), "http://169.254.169.254/latest/meta-data/".to_string(), "file:///etc/passwd".to_string(), "ftp://example.com/".to_string()] {
+			assert!(extractor.fetch_text(&url).await.is_err(), "{} was fetched", url);
+		}
+	}
+
+	#[test]
+	fn only_public_addresses_pass() {
+		for ip in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "::1", "fe80::1", "fd00::1", "::ffff:192.168.0.1"] {
+			assert!(!is_public_ip(ip.parse().unwrap()), "{} passed", ip);
+		}
+		for ip in ["93.184.216.34", "1.1.1.1", "2606:4700::1111"] {
+			assert!(is_public_ip(ip.parse().unwrap()), "{} was refused", ip);
+		}
+	}
+}
@@ -0,0 +1,174 @@
+#[cfg(feature = "ssr")]
+use std::{collections::HashMap, sync::Arc};
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+#[cfg(feature = "ssr")]
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "ssr")]
+use crate::config::Config;
+
+#[cfg(feature = "ssr")]
+use crate::db::{models::RunMetrics, repository};
+
+#[cfg(feature = "ssr")]
+use crate::services::{aggregator::AggregatorControl, content_extractor, hn_client::HnClient};
+
+// Polls the configured HN feeds and queues new stories for analysis
+#[cfg(feature = "ssr")]
+pub struct FetchWorker {
+	pub db_pool: sqlx::SqlitePool,
+	pub config: Arc<Config>,
+	pub hn: HnClient,
+	pub control: Arc<AggregatorControl>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Default)]
+struct FetchStats {
+	fetched: usize,
+	new: usize,
+	updated: usize,
+	skipped: usize,
+	failed: usize,
+}
+
+#[cfg(feature = "ssr")]
+impl FetchWorker {
+	pub async fn run(self, shutdown: CancellationToken) {
+		// First tick fires immediately, so fetching starts on startup
+		let period = Duration::from_secs(self.config.fetch_interval_minutes * 60);
+		let mut interval = interval(period);
+		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		self.control.fetch.schedule_next(Instant::now());
+
+		loop {
+			tokio::select! {
+				_ = shutdown.cancelled() => break,
+				_ = interval.tick() => {}
+				_ = self.control.refresh_requested.notified() => tracing::info!("Manual refresh requested"),
+			}
+
+			tracing::info!("Starting fetch cycle...");
+			self.control.fetch.start();
+			let started = Instant::now();
+			let run_id = repository::start_run(&self.db_pool, "fetch").await.inspect_err(|e| tracing::warn!("Failed to record fetch run: {}", e)).ok();
+
+			let mut metrics = RunMetrics::default();
+			match self.fetch_cycle(&shutdown).await {
+				Ok(stats) => {
+					tracing::info!("Fetch cycle completed, {} new and {} updated stories, {} skipped, {} failures", stats.new, stats.updated, stats.skipped, stats.failed);
+					self.control.fetch.finish(stats.new + stats.updated, stats.failed);
+					if stats.new + stats.updated > 0 {
+						self.control.analysis_wakeup.notify_one();
+					}
+					metrics.stories_fetched = stats.fetched as i64;
+					metrics.new_stories = stats.new as i64;
+					metrics.updated_stories = stats.updated as i64;
+				}
+				Err(e) => {
+					tracing::error!("Fetch cycle failed: {}", e);
+					self.control.fetch.finish(0, 1);
+					metrics.error = Some(format!("{:#}", e));
+				}
+			}
+
+			if let Some(run_id) = run_id {
+				metrics.duration_ms = started.elapsed().as_millis() as i64;
+				if let Err(e) = repository::finish_run(&self.db_pool, run_id, &metrics).await {
+					tracing::warn!("Failed to record fetch run: {}", e);
+				}
+			}
+
+			// Manual refreshes restart the schedule too, the next run is a full interval away
+			interval.reset();
+			self.control.fetch.schedule_next(Instant::now() + period);
+		}
+
+		tracing::info!("Fetch worker stopped");
+	}
+
+	async fn fetch_cycle(&self, shutdown: &CancellationToken) -> Result<FetchStats> {
+		let db_pool = &self.db_pool;
+		let config = self.config.as_ref();
+
+		// Step 1: Fetch story IDs from every configured feed, keeping the first feed each story appears in
+		let mut stats = FetchStats::default();
+		let mut story_feeds = HashMap::new();
+		for feed_config in &config.feeds {
+			tracing::info!("Fetching {} {} HN stories...", feed_config.limit, feed_config.feed.as_str());
+			match self.hn.fetch_stories(feed_config.feed, feed_config.limit).await {
+				Ok(ids) => {
+					for id in ids {
+						story_feeds.entry(id).or_insert(feed_config.feed);
+					}
+				}
+				Err(e) => {
+					tracing::warn!("Failed to fetch {} stories: {}", feed_config.feed.as_str(), e);
+					stats.failed += 1;
+				}
+			}
+		}
+		tracing::info!("Fetched {} story IDs", story_feeds.len());
+		stats.fetched = story_feeds.len();
+
+		// Step 2: Get details concurrently (bounded and rate limited by the client), abandoning them on shutdown
+		let items = tokio::select! {
+			_ = shutdown.cancelled() => return Ok(stats),
+			items = self.hn.fetch_items(story_feeds.keys().copied()) => items,
+		};
+
+		// Step 3: Save to database, each upsert is its own transaction so stopping between them is safe
+		for (id, result) in items {
+			if shutdown.is_cancelled() {
+				break;
+			}
+
+			let feed = story_feeds[&id];
+			match result {
+				Ok(item) if item.dead || item.deleted => {
+					tracing::debug!("Skipping dead or deleted item {}", id);
+					if let Err(e) = repository::mark_article_removed(db_pool, &item).await {
+						tracing::warn!("Failed to flag removed article {}: {}", id, e);
+					}
+					stats.skipped += 1;
+				}
+				Ok(item) if config.exclude_job_posts && item.item_type.as_deref() == Some("job") => {
+					tracing::debug!("Skipping job post {}", id);
+					stats.skipped += 1;
+				}
+				Ok(item) => {
+					// Ask/Show HN posts carry their body in `text`, use it as content directly
+					let content = item.text.as_deref().map(content_extractor::html_to_text).filter(|t| !t.is_empty());
+					match repository::upsert_article(db_pool, &item, feed, content.as_deref()).await {
+						Ok(true) => stats.new += 1,
+						Ok(false) => stats.updated += 1,
+						Err(e) => {
+							tracing::warn!("Failed to save article {}: {}", id, e);
+							stats.failed += 1;
+						}
+					}
+				}
+				Err(e) => {
+					tracing::warn!("Failed to fetch item {}: {}", id, e);
+					stats.failed += 1;
+				}
+			}
+		}
+
+		// Step 4: Drop score history that no sparkline reaches anymore
+		let keep_minutes = repository::SCORE_HISTORY_POINTS * config.fetch_interval_minutes as i64;
+		match repository::prune_score_snapshots(db_pool, keep_minutes).await {
+			Ok(0) => {}
+			Ok(pruned) => tracing::info!("Pruned {} old score snapshots", pruned),
+			Err(e) => tracing::warn!("Failed to prune score snapshots: {}", e),
+		}
+
+		Ok(stats)
+	}
+}
@@ -1,28 +1,158 @@
+#[cfg(feature = "ssr")]
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+#[cfg(feature = "ssr")]
+use futures::{stream, StreamExt};
+
 #[cfg(feature = "ssr")]
 use anyhow::Result;
 
 #[cfg(feature = "ssr")]
-use crate::db::models::HnItem;
+use crate::config::Config;
 
 #[cfg(feature = "ssr")]
-const HN_API_BASE: &str = "https://hacker-news.firebaseio.com/v0";
+use crate::db::models::{HnFeed, HnItem};
 
 #[cfg(feature = "ssr")]
-pub async fn fetch_top_stories(limit: usize) -> Result<Vec<i64>> {
-	let url = format!("{}/topstories.json", HN_API_BASE);
-	let client = reqwest::Client::new();
+use crate::metrics;
 
-	let story_ids: Vec<i64> = client.get(&url).send().await?.json().await?;
+#[cfg(feature = "ssr")]
+use crate::services::{rate_limiter::RateLimiter, retry::RetryPolicy};
 
-	Ok(story_ids.into_iter().take(limit).collect())
+// Client for the HN Firebase API (or any mirror exposing the same paths)
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+pub struct HnClient {
+	client: reqwest::Client,
+	base_url: String,
+	max_in_flight: usize,
+	limiter: Arc<RateLimiter>,
+	retry: RetryPolicy,
 }
 
 #[cfg(feature = "ssr")]
-pub async fn fetch_item(id: i64) -> Result<HnItem> {
-	let url = format!("{}/item/{}.json", HN_API_BASE, id);
-	let client = reqwest::Client::new();
+impl HnClient {
+	pub fn new(config: &Config) -> Result<Self> {
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.timeout(Duration::from_secs(30))
+			.pool_idle_timeout(Duration::from_secs(90))
+			.pool_max_idle_per_host(config.hn_max_concurrent_requests.max(1))
+			.user_agent(concat!("hn-smart-aggregator/", env!("CARGO_PKG_VERSION")))
+			.build()?;
+
+		Ok(Self::with_client(client, config))
+	}
+
+	// Use a caller-provided reqwest client (custom TLS, proxies, test servers...)
+	pub fn with_client(client: reqwest::Client, config: &Config) -> Self {
+		Self::with_settings(client, &config.hn_api_base, config.hn_max_concurrent_requests, config.hn_requests_per_second, RetryPolicy::from_config(config))
+	}
+
+	pub fn with_settings(client: reqwest::Client, base_url: &str, max_concurrent_requests: usize, requests_per_second: f64, retry: RetryPolicy) -> Self {
+		let max_in_flight = max_concurrent_requests.max(1);
+
+		Self {
+			client,
+			base_url: base_url.trim_end_matches('/').to_string(),
+			max_in_flight,
+			limiter: Arc::new(RateLimiter::new(requests_per_second, max_in_flight)),
+			retry,
+		}
+	}
+
+	pub async fn fetch_stories(&self, feed: HnFeed, limit: usize) -> Result<Vec<i64>> {
+		let url = format!("{}/{}stories.json", self.base_url, feed.as_str());
+
+		let started = Instant::now();
+		let result: Result<Vec<i64>> = self.retry.run("HN feed fetch", || self.get_json(&url)).await;
+		metrics::record_hn_request("feed", result.is_ok(), started.elapsed());
+		let story_ids = result?;
+
+		Ok(story_ids.into_iter().take(limit).collect())
+	}
+
+	pub async fn fetch_item(&self, id: i64) -> Result<HnItem> {
+		let url = format!("{}/item/{}.json", self.base_url, id);
+
+		let started = Instant::now();
+		let result = self.retry.run("HN item fetch", || self.get_json(&url)).await;
+		metrics::record_hn_request("item", result.is_ok(), started.elapsed());
+		result
+	}
+
+	// Fetch many items with at most `max_in_flight` requests running at once; results arrive in completion order
+	pub async fn fetch_items(&self, ids: impl IntoIterator<Item = i64>) -> Vec<(i64, Result<HnItem>)> {
+		stream::iter(ids).map(|id| async move { (id, self.fetch_item(id).await) }).buffer_unordered(self.max_in_flight).collect().await
+	}
+
+	// Every attempt, retries included, takes a rate limiter token
+	async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+		self.limiter.acquire().await;
+
+		Ok(self.client.get(url).send().await?.error_for_status()?.json().await?)
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+	use axum::{extract::Path, http::StatusCode, routing::get, Json, Router};
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	// Mock HN API mounted under a sub-path like a mirror would be; item 4 fails once before answering
+	async fn mock_api() -> String {
+		let flaky_calls = Arc::new(AtomicUsize::new(0));
+		let app = Router::new().route("/v0/topstories.json", get(|| async { Json(vec![3, 1, 2]) })).route(
+			"/v0/item/{file}",
+			get(move |Path(file): Path<String>| {
+				let flaky_calls = flaky_calls.clone();
+				async move {
+					let id: i64 = file.trim_end_matches(".json").parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+					if id == 4 && flaky_calls.fetch_add(1, Ordering::SeqCst) == 0 {
+						return Err(StatusCode::SERVICE_UNAVAILABLE);
+					}
+					if !matches!(id, 1 | 2 | 4) {
+						return Err(StatusCode::NOT_FOUND);
+					}
+					Ok(Json(serde_json::json!({ "id": id, "type": "story", "title": format!("Story {}", id), "time": 1_700_000_000 + id, "score": id * 10 })))
+				}
+			}),
+		);
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+		format!("http://{}", addr)
+	}
+
+	// Independent of the environment and config files of wherever the tests run
+	fn test_client(base: &str) -> HnClient {
+		let retry = RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(5) };
+		HnClient::with_settings(reqwest::Client::new(), &format!("{}/v0/", base), 2, 1000.0, retry)
+	}
+
+	#[tokio::test]
+	async fn fetch_stories_uses_the_configured_base_url() {
+		let client = test_client(&mock_api().await);
+		assert_eq!(client.fetch_stories(HnFeed::Top, 2).await.unwrap(), vec![3, 1]);
+	}
+
+	#[tokio::test]
+	async fn fetch_items_returns_a_result_per_id() {
+		let client = test_client(&mock_api().await);
 
-	let item = client.get(&url).send().await?.json().await?;
+		let mut results = client.fetch_items([1, 2, 3, 4]).await;
+		results.sort_by_key(|(id, _)| *id);
 
-	Ok(item)
+		let ids: Vec<i64> = results.iter().map(|(id, _)| *id).collect();
+		assert_eq!(ids, vec![1, 2, 3, 4]);
+		assert_eq!(results[0].1.as_ref().unwrap().title.as_deref(), Some("Story 1"));
+		assert_eq!(results[1].1.as_ref().unwrap().score, Some(20));
+		assert!(results[2].1.is_err());
+		// Retried after the 503
+		assert_eq!(results[3].1.as_ref().unwrap().id, 4);
+	}
 }
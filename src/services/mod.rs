@@ -1,3 +1,12 @@
 pub mod aggregator;
+pub mod analysis_worker;
+pub mod analyzer;
+pub mod circuit_breaker;
+pub mod content_extractor;
+pub mod fetch_worker;
 pub mod hn_client;
 pub mod ollama_client;
+pub mod openai_client;
+pub mod rate_limiter;
+pub mod retry;
+pub mod rule_based;
@@ -5,7 +5,10 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ssr")]
-use crate::db::models::{AnalysisResult, Article};
+use crate::db::models::{AnalysisResult, Article, FeedbackExample};
+
+#[cfg(feature = "ssr")]
+use crate::services::analyzer::{self, Analyzer};
 
 #[cfg(feature = "ssr")]
 #[derive(Debug, Serialize)]
@@ -13,7 +16,8 @@ struct OllamaRequest {
 	model: String,
 	messages: Vec<Message>,
 	stream: bool,
-	format: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	format: Option<String>,
 }
 
 #[cfg(feature = "ssr")]
@@ -36,67 +40,100 @@ struct MessageContent {
 }
 
 #[cfg(feature = "ssr")]
-pub async fn analyze_article(persona: &str, article: &Article, categories: &[String], ollama_url: &str, model: &str) -> Result<AnalysisResult> {
-	let categories_str = categories.join(", ");
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+	models: Vec<ModelTag>,
+}
 
-	let prompt = format!(
-		r#"Analyze this Hacker News article.
-Assign the most specific category from the list below.
-Use 'Other' ONLY for news that does not fit any other category.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Deserialize)]
+struct ModelTag {
+	name: String,
+}
 
-Output Format (JSON):
-{{"relevant": boolean, "reason": "explanation", "priority": number (1-5), "category": "category_name"}}
+// Analyzer backed by Ollama's native /api/chat endpoint
+#[cfg(feature = "ssr")]
+pub struct OllamaAnalyzer {
+	client: reqwest::Client,
+	base_url: String,
+	model: String,
+}
 
-Persona: {}
+#[cfg(feature = "ssr")]
+impl OllamaAnalyzer {
+	pub fn new(base_url: &str, model: &str) -> Result<Self> {
+		let client = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build()?;
 
-Available Categories (Strict): {}
+		Ok(Self { client, base_url: base_url.trim_end_matches('/').to_string(), model: model.to_string() })
+	}
 
-Article Title: {}
-Article URL: {}"#,
-		persona,
-		categories_str,
-		article.title,
-		article.url.as_deref().unwrap_or("N/A")
-	);
+	// Single-turn chat, optionally constrained to JSON output
+	async fn chat(&self, prompt: String, json: bool) -> Result<String> {
+		let request = OllamaRequest { model: self.model.clone(), messages: vec![Message { role: "user".to_string(), content: prompt }], stream: false, format: json.then(|| "json".to_string()) };
+
+		let response = self
+			.client
+			.post(format!("{}/api/chat", self.base_url))
+			.json(&request)
+			.send()
+			.await
+			.context("Failed to send request to Ollama")?
+			.error_for_status()
+			.context("Ollama returned an error")?
+			.json::<OllamaResponse>()
+			.await
+			.context("Failed to parse Ollama response")?;
+
+		Ok(response.message.content)
+	}
+}
 
-	let request = OllamaRequest { model: model.to_string(), messages: vec![Message { role: "user".to_string(), content: prompt }], stream: false, format: "json".to_string() };
+#[cfg(feature = "ssr")]
+#[async_trait::async_trait]
+impl Analyzer for OllamaAnalyzer {
+	fn name(&self) -> &'static str {
+		"ollama"
+	}
 
-	let client = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build()?;
+	fn model(&self) -> String {
+		format!("{}:{}", self.name(), self.model)
+	}
 
-	let response = client
-		.post(format!("{}/api/chat", ollama_url))
-		.json(&request)
-		.send()
-		.await
-		.context("Failed to send request to Ollama")?
-		.json::<OllamaResponse>()
-		.await
-		.context("Failed to parse Ollama response")?;
+	async fn analyze(&self, persona: &str, article: &Article, categories: &[String], examples: &[FeedbackExample]) -> Result<AnalysisResult> {
+		let prompt = analyzer::build_prompt(persona, article, categories, examples);
+		let content = self.chat(prompt, true).await?;
 
-	let content = &response.message.content;
+		analyzer::parse_analysis(&content)
+	}
 
-	// Try to extract JSON from response (in case there's extra text)
-	let json_str = extract_json(content);
+	fn supports_summaries(&self) -> bool {
+		true
+	}
 
-	// Log for debugging if parsing fails
-	let analysis: AnalysisResult = serde_json::from_str(&json_str).map_err(|e| {
-		tracing::error!("Failed to parse JSON. Error: {}. Raw content: {}", e, content);
-		anyhow::anyhow!("Failed to parse analysis JSON")
-	})?;
+	async fn summarize_discussion(&self, article: &Article, comments: &[String]) -> Result<String> {
+		let prompt = analyzer::build_discussion_prompt(article, comments);
 
-	Ok(analysis)
-}
+		Ok(self.chat(prompt, false).await?.trim().to_string())
+	}
 
-#[cfg(feature = "ssr")]
-fn extract_json(content: &str) -> String {
-	// Try to find JSON object in the content
-	if let Some(start) = content.find('{') {
-		if let Some(end) = content.rfind('}') {
-			if start < end {
-				return content[start..=end].to_string();
-			}
-		}
+	// Ollama is up and the configured model has been pulled
+	async fn health_check(&self) -> Result<()> {
+		let tags = self
+			.client
+			.get(format!("{}/api/tags", self.base_url))
+			.timeout(std::time::Duration::from_secs(5))
+			.send()
+			.await
+			.context("Ollama is unreachable")?
+			.error_for_status()?
+			.json::<TagsResponse>()
+			.await
+			.context("Failed to parse Ollama model list")?;
+
+		// Ollama reports untagged models as "name:latest"
+		let pulled = tags.models.iter().any(|m| m.name == self.model || m.name == format!("{}:latest", self.model));
+		anyhow::ensure!(pulled, "Model '{}' is not pulled on Ollama", self.model);
+
+		Ok(())
 	}
-	// If no JSON found, return original content
-	content.to_string()
 }
@@ -0,0 +1,140 @@
+#[cfg(feature = "ssr")]
+use anyhow::{Context, Result};
+
+#[cfg(feature = "ssr")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::db::models::{AnalysisResult, Article, FeedbackExample};
+
+#[cfg(feature = "ssr")]
+use crate::services::analyzer::{self, Analyzer};
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+	model: String,
+	messages: Vec<Message>,
+	stream: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	response_format: Option<ResponseFormat>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize)]
+struct ResponseFormat {
+	#[serde(rename = "type")]
+	kind: String,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize)]
+struct Message {
+	role: String,
+	content: String,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+	choices: Vec<Choice>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Deserialize)]
+struct Choice {
+	message: MessageContent,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Deserialize)]
+struct MessageContent {
+	#[serde(default)]
+	content: Option<String>,
+}
+
+// Analyzer for any server speaking the OpenAI /v1/chat/completions format (llama.cpp, vLLM, LM Studio...)
+#[cfg(feature = "ssr")]
+pub struct OpenAiAnalyzer {
+	client: reqwest::Client,
+	base_url: String,
+	model: String,
+	api_key: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl OpenAiAnalyzer {
+	pub fn new(base_url: &str, model: &str, api_key: Option<&str>) -> Result<Self> {
+		let client = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build()?;
+
+		Ok(Self { client, base_url: base_url.trim_end_matches('/').to_string(), model: model.to_string(), api_key: api_key.map(str::to_string) })
+	}
+
+	// Single-turn chat completion, optionally constrained to a JSON object
+	async fn chat(&self, prompt: String, json: bool) -> Result<String> {
+		let request = ChatCompletionRequest {
+			model: self.model.clone(),
+			messages: vec![Message { role: "user".to_string(), content: prompt }],
+			stream: false,
+			response_format: json.then(|| ResponseFormat { kind: "json_object".to_string() }),
+		};
+
+		let mut builder = self.client.post(format!("{}/v1/chat/completions", self.base_url)).json(&request);
+		if let Some(key) = &self.api_key {
+			builder = builder.bearer_auth(key);
+		}
+
+		let response = builder
+			.send()
+			.await
+			.context("Failed to send request to OpenAI-compatible server")?
+			.error_for_status()
+			.context("OpenAI-compatible server returned an error")?
+			.json::<ChatCompletionResponse>()
+			.await
+			.context("Failed to parse OpenAI-compatible response")?;
+
+		response.choices.into_iter().next().and_then(|c| c.message.content).context("OpenAI-compatible response contained no message")
+	}
+}
+
+#[cfg(feature = "ssr")]
+#[async_trait::async_trait]
+impl Analyzer for OpenAiAnalyzer {
+	fn name(&self) -> &'static str {
+		"openai"
+	}
+
+	fn model(&self) -> String {
+		format!("{}:{}", self.name(), self.model)
+	}
+
+	async fn analyze(&self, persona: &str, article: &Article, categories: &[String], examples: &[FeedbackExample]) -> Result<AnalysisResult> {
+		let prompt = analyzer::build_prompt(persona, article, categories, examples);
+		let content = self.chat(prompt, true).await?;
+
+		analyzer::parse_analysis(&content)
+	}
+
+	fn supports_summaries(&self) -> bool {
+		true
+	}
+
+	async fn summarize_discussion(&self, article: &Article, comments: &[String]) -> Result<String> {
+		let prompt = analyzer::build_discussion_prompt(article, comments);
+
+		Ok(self.chat(prompt, false).await?.trim().to_string())
+	}
+
+	// The models list is the cheapest authenticated call every compatible server implements
+	async fn health_check(&self) -> Result<()> {
+		let mut builder = self.client.get(format!("{}/v1/models", self.base_url)).timeout(std::time::Duration::from_secs(5));
+		if let Some(key) = &self.api_key {
+			builder = builder.bearer_auth(key);
+		}
+
+		builder.send().await.context("OpenAI-compatible server is unreachable")?.error_for_status()?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,89 @@
+#[cfg(feature = "ssr")]
+use tokio::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+// Token bucket: refills `rate` tokens per second up to `burst`, one token per request
+#[cfg(feature = "ssr")]
+pub struct RateLimiter {
+	rate: f64,
+	burst: f64,
+	state: Mutex<Bucket>,
+}
+
+#[cfg(feature = "ssr")]
+struct Bucket {
+	tokens: f64,
+	last_refill: Instant,
+}
+
+#[cfg(feature = "ssr")]
+impl RateLimiter {
+	pub fn new(rate: f64, burst: usize) -> Self {
+		let burst = burst.max(1) as f64;
+		Self { rate: rate.max(0.001), burst, state: Mutex::new(Bucket { tokens: burst, last_refill: Instant::now() }) }
+	}
+
+	// Wait until a token is available and take it
+	pub async fn acquire(&self) {
+		loop {
+			let wait = {
+				let mut bucket = self.state.lock().await;
+				let now = Instant::now();
+				let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+				bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+				bucket.last_refill = now;
+
+				if bucket.tokens >= 1.0 {
+					bucket.tokens -= 1.0;
+					return;
+				}
+
+				Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+			};
+
+			tokio::time::sleep(wait).await;
+		}
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[tokio::test(start_paused = true)]
+	async fn burst_is_available_immediately() {
+		let limiter = RateLimiter::new(1.0, 3);
+		let started = Instant::now();
+		for _ in 0..3 {
+			limiter.acquire().await;
+		}
+		assert_eq!(started.elapsed(), Duration::ZERO);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn requests_past_the_burst_wait_for_refills() {
+		let limiter = RateLimiter::new(2.0, 2);
+		let started = Instant::now();
+		for _ in 0..5 {
+			limiter.acquire().await;
+		}
+		// Two from the bucket, then one every 500ms
+		let elapsed = started.elapsed();
+		assert!(elapsed >= Duration::from_millis(1500) && elapsed < Duration::from_millis(1600), "{:?}", elapsed);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn idle_time_refills_no_more_than_the_burst() {
+		let limiter = RateLimiter::new(10.0, 2);
+		tokio::time::sleep(Duration::from_secs(60)).await;
+
+		let started = Instant::now();
+		for _ in 0..3 {
+			limiter.acquire().await;
+		}
+		let elapsed = started.elapsed();
+		assert!(elapsed >= Duration::from_millis(100) && elapsed < Duration::from_millis(150), "{:?}", elapsed);
+	}
+}
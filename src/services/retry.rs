@@ -0,0 +1,144 @@
+#[cfg(feature = "ssr")]
+use std::future::Future;
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use tokio::time::Duration;
+
+#[cfg(feature = "ssr")]
+use crate::config::Config;
+
+// Exponential backoff with jitter for flaky upstream calls
+#[cfg(feature = "ssr")]
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+	pub max_attempts: u32,
+	pub base_delay: Duration,
+	pub max_delay: Duration,
+}
+
+#[cfg(feature = "ssr")]
+impl RetryPolicy {
+	pub fn from_config(config: &Config) -> Self {
+		Self {
+			max_attempts: config.retry_max_attempts.max(1),
+			base_delay: Duration::from_millis(config.retry_base_delay_ms),
+			max_delay: Duration::from_millis(config.retry_max_delay_ms),
+		}
+	}
+
+	// Delay before retry number `attempt` (1-based): base * 2^(attempt-1), capped, then jittered into [50%, 100%]
+	pub fn delay_for(&self, attempt: u32) -> Duration {
+		let exponential = self.base_delay.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+		let capped = exponential.min(self.max_delay);
+		capped.mul_f64(0.5 + rand::random::<f64>() * 0.5)
+	}
+
+	pub async fn run<T, F, Fut>(&self, what: &str, mut op: F) -> Result<T>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<T>>,
+	{
+		let mut attempt = 1;
+		loop {
+			match op().await {
+				Ok(value) => return Ok(value),
+				Err(e) if attempt < self.max_attempts && is_transient(&e) => {
+					let delay = self.delay_for(attempt);
+					tracing::debug!("{} failed (attempt {}/{}): {}. Retrying in {:?}", what, attempt, self.max_attempts, e, delay);
+					tokio::time::sleep(delay).await;
+					attempt += 1;
+				}
+				Err(e) => return Err(e),
+			}
+		}
+	}
+}
+
+// Transport errors, timeouts, 5xx and 429 can go away on their own; other 4xx and unparseable answers won't
+#[cfg(feature = "ssr")]
+pub fn is_transient(err: &anyhow::Error) -> bool {
+	err.chain().find_map(|cause| cause.downcast_ref::<reqwest::Error>()).is_some_and(|e| match e.status() {
+		Some(status) => status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+		None => !(e.is_decode() || e.is_builder() || e.is_redirect()),
+	})
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+	use anyhow::Context;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	fn policy(max_attempts: u32) -> RetryPolicy {
+		RetryPolicy { max_attempts, base_delay: Duration::from_millis(100), max_delay: Duration::from_secs(1) }
+	}
+
+	fn status_error(status: u16) -> anyhow::Error {
+		let response = reqwest::Response::from(axum::http::Response::builder().status(status).body("").unwrap());
+		anyhow::Error::from(response.error_for_status().unwrap_err()).context("Upstream call failed")
+	}
+
+	async fn connect_error() -> anyhow::Error {
+		// Nothing listens on port 1
+		reqwest::Client::new().get("http://127.0.0.1:1/").send().await.context("Failed to send request").unwrap_err()
+	}
+
+	#[test]
+	fn delay_doubles_up_to_the_cap_with_jitter() {
+		let policy = policy(5);
+		for _ in 0..50 {
+			let first = policy.delay_for(1);
+			assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100), "{:?}", first);
+			let third = policy.delay_for(3);
+			assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400), "{:?}", third);
+			let capped = policy.delay_for(40);
+			assert!(capped >= Duration::from_millis(500) && capped <= Duration::from_secs(1), "{:?}", capped);
+		}
+	}
+
+	#[tokio::test]
+	async fn classifies_errors() {
+		assert!(is_transient(&connect_error().await));
+		assert!(is_transient(&status_error(503)));
+		assert!(is_transient(&status_error(429)));
+		assert!(!is_transient(&status_error(404)));
+		assert!(!is_transient(&status_error(400)));
+		assert!(!is_transient(&anyhow::anyhow!("Failed to parse analysis JSON")));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn run_retries_only_transient_errors() {
+		let calls = AtomicU32::new(0);
+		let result: Result<()> = policy(3)
+			.run("test", || async {
+				calls.fetch_add(1, Ordering::SeqCst);
+				Err(status_error(502))
+			})
+			.await;
+		assert!(result.is_err());
+		assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+		let result: Result<()> = policy(3)
+			.run("test", || async {
+				calls.fetch_add(1, Ordering::SeqCst);
+				Err(status_error(422))
+			})
+			.await;
+		assert!(result.is_err());
+		assert_eq!(calls.swap(0, Ordering::SeqCst), 1);
+
+		let result = policy(3)
+			.run("test", || async {
+				match calls.fetch_add(1, Ordering::SeqCst) {
+					0 => Err(status_error(503)),
+					_ => Ok(7),
+				}
+			})
+			.await;
+		assert_eq!(result.unwrap(), 7);
+		assert_eq!(calls.load(Ordering::SeqCst), 2);
+	}
+}
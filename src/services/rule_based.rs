@@ -0,0 +1,64 @@
+#[cfg(feature = "ssr")]
+use std::collections::HashSet;
+
+#[cfg(feature = "ssr")]
+use anyhow::Result;
+
+#[cfg(feature = "ssr")]
+use crate::db::models::{AnalysisResult, Article, FeedbackExample};
+
+#[cfg(feature = "ssr")]
+use crate::services::analyzer::Analyzer;
+
+// Words too common to say anything about a persona's interests
+#[cfg(feature = "ssr")]
+const STOP_WORDS: &[&str] = &[
+	"about", "also", "and", "any", "are", "articles", "for", "from", "have", "interested", "into", "like", "more", "news", "not", "that", "the", "their", "them", "then", "they",
+	"this", "want", "what", "when", "which", "with", "you", "your",
+];
+
+// Deterministic keyword matcher, usable without any inference server
+#[cfg(feature = "ssr")]
+pub struct RuleBasedAnalyzer;
+
+#[cfg(feature = "ssr")]
+#[async_trait::async_trait]
+impl Analyzer for RuleBasedAnalyzer {
+	fn name(&self) -> &'static str {
+		"rule-based"
+	}
+
+	async fn analyze(&self, persona: &str, article: &Article, categories: &[String], _examples: &[FeedbackExample]) -> Result<AnalysisResult> {
+		let title_words = keywords(&article.title);
+		let persona_words = keywords(persona);
+
+		let mut matched: Vec<&String> = title_words.intersection(&persona_words).collect();
+		matched.sort();
+
+		// First category sharing a keyword with the title, "Other" otherwise
+		let category = categories
+			.iter()
+			.filter(|c| !c.eq_ignore_ascii_case("other"))
+			.find(|c| !keywords(c).is_disjoint(&title_words))
+			.cloned()
+			.unwrap_or_else(|| "Other".to_string());
+
+		let relevant = !matched.is_empty();
+		let priority = (1 + matched.len() as i64).min(5);
+		let reason = if relevant {
+			format!("Title matches persona keywords: {}", matched.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "))
+		} else {
+			"No persona keywords found in title".to_string()
+		};
+
+		Ok(AnalysisResult { relevant, reason, priority, category, summary: None, key_points: Vec::new() })
+	}
+}
+
+#[cfg(feature = "ssr")]
+fn keywords(text: &str) -> HashSet<String> {
+	text.split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+		.map(|w| w.to_lowercase())
+		.filter(|w| w.len() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+		.collect()
+}
@@ -1,12 +1,22 @@
 #[cfg(feature = "ssr")]
 use std::sync::Arc;
 
+#[cfg(feature = "ssr")]
+use crate::auth::LoginThrottle;
+
 #[cfg(feature = "ssr")]
 use crate::config::Config;
 
+#[cfg(feature = "ssr")]
+use crate::services::{aggregator::AggregatorControl, analyzer::Analyzer, circuit_breaker::CircuitBreaker};
+
 #[cfg(feature = "ssr")]
 #[derive(Clone)]
 pub struct AppState {
 	pub db_pool: sqlx::SqlitePool,
 	pub config: Arc<Config>,
+	pub analyzer: Arc<dyn Analyzer>,
+	pub analyzer_breaker: Arc<CircuitBreaker>,
+	pub aggregator: Arc<AggregatorControl>,
+	pub login_throttle: Arc<LoginThrottle>,
 }